@@ -3,11 +3,13 @@
 //! Associação com o livro (bellman_ford())
 
 pub use crate::graph::Graph;
+pub use crate::graph_trait::WeightedGraph;
+pub use crate::weight::Weight;
 
 /// # Algoritmo e associação com o livro.
 /// ## Argumentos
 /// 
-/// "grafo" - O grafo analisado;
+/// "grafo" - O grafo analisado (qualquer representação que implemente WeightedGraph);
 /// "origem" - Vértice origem do caminho.
 ///
 /// ## Retorno
@@ -15,15 +17,25 @@ pub use crate::graph::Graph;
 /// Vetor de custos para os demais vértices;\
 /// Vetor de anteriores, para a reconstruçao do caminho.
 /// 
+/// ## Vértices inalcançáveis
+/// Continuam com INFINITO e sem anterior, mesmo que tenham arestas negativas saindo deles.
+///
+/// ## Grafos não-direcionados
+/// Uma aresta não-direcionada é percorrida nos dois sentidos, então qualquer aresta negativa já forma um ciclo negativo (ida e volta) e o algoritmo entra em pânico.
+/// 
+/// ## Desempate
+/// As arestas são relaxadas sempre na mesma ordem: origens em ordem crescente e, em cada origem, os vizinhos na ordem em que as arestas foram incluídas.\
+/// Como d[i] só muda com uma distância estritamente menor, entre dois caminhos de mesmo custo fica o anterior da primeira relaxação que chegou ao custo final, e o resultado é o mesmo em toda execução.
+/// 
 /// ## Associação com livro
 /// ```rust
 /// let num_v = grafo.num_vertex();
 ///
 ///    // Linha 1: Cria um vetor de distâncias, inicializando todos com INFINITO. Em seguida a distancia com a origem recebe zero
 ///    //é criado tambem o vetor anterior
-///    let mut d = vec![i32::MAX/2; num_v + 1];
-///    d[*origem] = 0;
-///    let mut anterior = vec![None; num_v + 1];
+///    let mut d = vec![W::infinity(); num_v];
+///    d[*origem] = W::zero();
+///    let mut anterior = vec![None; num_v];
 ///
 ///    //Linha 2: Inicio do loop, enquanto existir uma aresta (j,i) no grafo tal que d[i] > d[j] + vij (peso) fazer
 ///    let mut mudou = true;
@@ -31,26 +43,22 @@ pub use crate::graph::Graph;
 ///    while mudou && (iteracoes < num_v) {
 ///        mudou = false;
 ///
-///        for(j, lista_vizinhos) in &grafo.adj {
-///            let mut head = lista_vizinhos.as_ref();
-///
-///            while let Some(node) = head {
-///                let i = node.value;
-///                let v = node.weight;
-///
-///                if d[i] > d[*j] + v{
+///        for j in grafo.vertices_list() {
+///            if d[j] == W::infinity() { // j ainda não foi alcançado: nos inteiros INFINITO + aresta negativa daria um custo finito
+///                continue;
+///            }
+///            for (i, v) in grafo.neighbors_with_weights(j) {
+///                if d[i] > d[j] + v{
 ///                    //Checagem pra ver se o grafo tem ciclo negativo
 ///                    if iteracoes == num_v - 1{ //Na iteração V (lembre que iteracoes começou com 0), se houver outra mudança é porque o grafo tem ciclo negativo
 ///                        panic!("There is a negative cycle in the graph.");
 ///                    }
 ///
 ///                    //Linha 3: d[i] recebe d[j] + vij e anterior[i] recebe j
-///                    d[i] = d[*j] + v;
-///                    anterior[i] = Some(*j);
+///                    d[i] = d[j] + v;
+///                    anterior[i] = Some(j);
 ///                    mudou = true;
 ///                }
-///
-///                head = node.next.as_ref();
 ///            }
 ///        }
 ///        iteracoes += 1;
@@ -58,14 +66,14 @@ pub use crate::graph::Graph;
 ///
 ///    (d, anterior) // Retorno
 /// ```
-pub fn bellman_ford(grafo: &Graph, origem: &usize) -> (Vec<i32>, Vec<Option<usize>>) { //retorna as distancias e anteriores pros caminhos
+pub fn bellman_ford<W: Weight, G: WeightedGraph<W>>(grafo: &G, origem: &usize) -> (Vec<W>, Vec<Option<usize>>) { //retorna as distancias e anteriores pros caminhos
     let num_v = grafo.num_vertex();
 
     // Linha 1: Cria um vetor de distâncias, inicializando todos com INFINITO. Em seguida a distancia com a origem recebe zero
     //é criado tambem o vetor anterior
-    let mut d = vec![i32::MAX/2; num_v + 1];
-    d[*origem] = 0;
-    let mut anterior = vec![None; num_v + 1];
+    let mut d = vec![W::infinity(); num_v];
+    d[*origem] = W::zero();
+    let mut anterior = vec![None; num_v];
 
     //Linha 2: Inicio do loop, enquanto existir uma aresta (j,i) no grafo tal que d[i] > d[j] + vij (peso) fazer
     let mut mudou = true;
@@ -73,26 +81,22 @@ pub fn bellman_ford(grafo: &Graph, origem: &usize) -> (Vec<i32>, Vec<Option<usiz
     while mudou && (iteracoes < num_v) {
         mudou = false;
 
-        for(j, lista_vizinhos) in &grafo.adj {
-            let mut head = lista_vizinhos.as_ref();
-
-            while let Some(node) = head {
-                let i = node.value;
-                let v = node.weight;
-
-                if d[i] > d[*j] + v{
+        for j in grafo.vertices_list() {
+            if d[j] == W::infinity() { // j ainda não foi alcançado: nos inteiros INFINITO + aresta negativa daria um custo finito
+                continue;
+            }
+            for (i, v) in grafo.neighbors_with_weights(j) {
+                if d[i] > d[j] + v{
                     //Checagem pra ver se o grafo tem ciclo negativo
                     if iteracoes == num_v - 1{ //Na iteração V (lembre que iteracoes começou com 0), se houver outra mudança é porque o grafo tem ciclo negativo
                         panic!("There is a negative cycle in the graph.");
                     }
 
                     //Linha 3: d[i] recebe d[j] + vij e anterior[i] recebe j
-                    d[i] = d[*j] + v;
-                    anterior[i] = Some(*j);
+                    d[i] = d[j] + v;
+                    anterior[i] = Some(j);
                     mudou = true;
                 }
-
-                head = node.next.as_ref();
             }
         }
         iteracoes += 1;
@@ -100,3 +104,29 @@ pub fn bellman_ford(grafo: &Graph, origem: &usize) -> (Vec<i32>, Vec<Option<usiz
 
     (d, anterior) // Retorno
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vertice_inalcancavel_com_aresta_negativa_continua_infinito() {
+        // Só a aresta 1 -> 2 (-5), e nenhuma aresta sai da origem 0
+        let mut gr: Graph = Graph::new(true, 3);
+        gr.edge(1, 2, -5).unwrap();
+        let (d, anterior) = bellman_ford(&gr, &0);
+        assert_eq!(d, vec![0, i32::infinity(), i32::infinity()]);
+        assert_eq!(anterior, vec![None, None, None]);
+    }
+
+    #[test]
+    fn aresta_negativa_alcancavel_continua_valendo() {
+        let mut gr: Graph = Graph::new(true, 4);
+        gr.edge(0, 1, 4).unwrap();
+        gr.edge(1, 2, -5).unwrap();
+        gr.edge(3, 0, -7).unwrap();
+        let (d, anterior) = bellman_ford(&gr, &0);
+        assert_eq!(d, vec![0, 4, -1, i32::infinity()]);
+        assert_eq!(anterior, vec![None, Some(0), Some(1), None]);
+    }
+}
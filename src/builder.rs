@@ -0,0 +1,214 @@
+//! # Construção validada do grafo
+//! ## Conteúdo:
+//! Política para laços (SelfLoops);\
+//! Construtor que valida cada aresta (GraphBuilder);\
+//! Grafo pronto e imutável, com as garantias do construtor (ValidatedGraph).
+//!
+//! O Graph::edge() aceita qualquer usize: uma aresta para o vértice 500 em um grafo de 12 vértices é guardada sem reclamar e só estoura depois, dentro do floyd_rot_n_cost(). O GraphBuilder recusa a aresta na hora:
+//! ```rust
+//!    let mut builder = GraphBuilder::new(12).undirected().self_loops(SelfLoops::Reject);
+//!    builder.edge(0, 1, 17)?.edge(0, 2, 25)?;
+//!    builder.edge(0, 500, 3)?; // Err(GraphError::VertexOutOfRange { vertex: 500, num_vertex: 12 })
+//!    let gr = builder.build();
+//!    let (distancias, anteriores) = dijikstra(&gr, &0);
+//! ```
+
+use std::ops::Deref;
+
+pub use crate::error::GraphError;
+pub use crate::graph::{Graph, ParallelEdges};
+pub use crate::graph_trait::WeightedGraph;
+pub use crate::weight::Weight;
+
+/// # Política para laços (arestas de um vértice para ele mesmo).
+/// ## Variantes
+/// "Allow" - Guarda o laço (padrão, como no Graph);\
+/// "Reject" - Recusa o laço com GraphError::SelfLoop.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelfLoops {
+    #[default]
+    Allow,
+    Reject,
+}
+
+/// # Construtor do grafo que valida cada aresta antes de incluí-la.
+/// ## Garantias
+/// Todo vértice das arestas e dos nomes está em 0..num_vertex;\
+/// Toda aresta tem um valor para cada atributo;\
+/// Laços e arestas paralelas seguem as políticas escolhidas.
+///
+/// As configurações (undirected(), one_based(), attributes(), parallel_edges() e self_loops()) consomem o construtor e vêm antes das arestas.
+
+#[derive(Debug)]
+pub struct GraphBuilder<W = i32> {
+    grafo: Graph<W>,
+    self_loops: SelfLoops,
+}
+
+impl<W: Weight> GraphBuilder<W> {
+
+    /// # Começa um grafo direcionado, 0-based, com num_vertex vértices e só o atributo "weight"
+
+    pub fn new(num_vertex: usize) -> Self {
+        GraphBuilder { grafo: Graph::new(true, num_vertex), self_loops: SelfLoops::Allow }
+    }
+
+    /// # Torna o grafo não-direcionado
+
+    pub fn undirected(mut self) -> Self {
+        self.grafo.is_directed = false;
+        self
+    }
+
+    /// # Mostra os vértices como 1-based (os vértices das arestas continuam 0-based, como em todo o Graph)
+
+    pub fn one_based(mut self) -> Self {
+        self.grafo.is_zero_based = false;
+        self
+    }
+
+    /// # Troca os atributos das arestas (o primeiro é o peso)
+
+    pub fn attributes(mut self, nomes: &[&str]) -> Self {
+        assert!(!nomes.is_empty(), "At least one attribute is required.");
+        assert_eq!(self.grafo.num_edges, 0, "Attributes must be set before any edge.");
+        self.grafo.attribute_names = nomes.iter().map(|nome| nome.to_string()).collect();
+        self
+    }
+
+    /// # Escolhe a política para arestas paralelas (KeepAll por padrão)
+
+    pub fn parallel_edges(mut self, politica: ParallelEdges) -> Self {
+        self.grafo.parallel_edges = politica;
+        self
+    }
+
+    /// # Escolhe a política para laços (Allow por padrão)
+
+    pub fn self_loops(mut self, politica: SelfLoops) -> Self {
+        self.self_loops = politica;
+        self
+    }
+
+    /// # Inclui uma aresta só com o peso (os demais atributos ficam com zero)
+
+    pub fn edge(&mut self, origem: usize, destino: usize, peso: W) -> Result<&mut Self, GraphError> {
+        let mut atributos = vec![W::zero(); self.grafo.attribute_names.len()];
+        atributos[0] = peso;
+        self.edge_with_attributes(origem, destino, &atributos)
+    }
+
+    /// # Inclui uma aresta com todos os atributos, na ordem de attribute_names
+    /// ## Erros
+    /// VertexOutOfRange, WrongAttributeCount, SelfLoop (com SelfLoops::Reject) ou DuplicateEdge (com ParallelEdges::Reject). Com erro o grafo não muda.
+
+    pub fn edge_with_attributes(&mut self, origem: usize, destino: usize, atributos: &[W]) -> Result<&mut Self, GraphError> {
+        self.checar_vertice(origem)?;
+        self.checar_vertice(destino)?;
+        if atributos.len() != self.grafo.attribute_names.len() {
+            return Err(GraphError::WrongAttributeCount { expected: self.grafo.attribute_names.len(), found: atributos.len() });
+        }
+        if origem == destino && self.self_loops == SelfLoops::Reject {
+            return Err(GraphError::SelfLoop { vertex: origem });
+        }
+        self.grafo.edge_with_attributes(origem, destino, atributos)?;
+        Ok(self)
+    }
+
+    /// # Dá um nome ao vértice
+    /// ## Erros
+    /// VertexOutOfRange, ou DuplicateLabel se outro vértice já tiver esse nome.
+
+    pub fn label(&mut self, vertice: usize, nome: &str) -> Result<&mut Self, GraphError> {
+        self.checar_vertice(vertice)?;
+        if self.grafo.vertex_by_label(nome).is_some_and(|outro| outro != vertice) {
+            return Err(GraphError::DuplicateLabel(nome.to_string()));
+        }
+        self.grafo.set_label(vertice, nome);
+        Ok(self)
+    }
+
+    /// # Termina a construção
+
+    pub fn build(self) -> ValidatedGraph<W> {
+        ValidatedGraph { grafo: self.grafo }
+    }
+
+    fn checar_vertice(&self, vertice: usize) -> Result<(), GraphError> {
+        if vertice < self.grafo.num_vertex {
+            Ok(())
+        } else {
+            Err(GraphError::VertexOutOfRange { vertex: vertice, num_vertex: self.grafo.num_vertex })
+        }
+    }
+}
+
+/// # Grafo imutável criado pelo GraphBuilder.
+/// Dá acesso de leitura a todo o Graph (via Deref) e roda direto nos algoritmos, mas não pode ser alterado, então as garantias do construtor continuam valendo.\
+/// Para alterar, into_inner() devolve o Graph (e as garantias deixam de valer).
+
+#[derive(Debug)]
+pub struct ValidatedGraph<W = i32> {
+    grafo: Graph<W>,
+}
+
+impl<W> ValidatedGraph<W> {
+
+    /// # Devolve o Graph por dentro, que volta a aceitar qualquer aresta
+
+    pub fn into_inner(self) -> Graph<W> {
+        self.grafo
+    }
+}
+
+impl<W> Deref for ValidatedGraph<W> {
+    type Target = Graph<W>;
+
+    fn deref(&self) -> &Graph<W> {
+        &self.grafo
+    }
+}
+
+impl<W: Weight> WeightedGraph<W> for ValidatedGraph<W> {
+    fn num_vertex(&self) -> usize {
+        self.grafo.num_vertex
+    }
+
+    fn neighbors_with_weights(&self, vertice: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        self.grafo.neighbors_with_weights(vertice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arestas_invalidas_sao_recusadas_sem_mudar_o_grafo() {
+        let mut builder: GraphBuilder = GraphBuilder::new(3).attributes(&["distance", "time"]).self_loops(SelfLoops::Reject).parallel_edges(ParallelEdges::Reject);
+        builder.edge_with_attributes(0, 1, &[5, 2]).unwrap().edge(1, 2, 4).unwrap();
+
+        assert!(matches!(builder.edge(0, 3, 1), Err(GraphError::VertexOutOfRange { vertex: 3, num_vertex: 3 })));
+        assert!(matches!(builder.edge_with_attributes(0, 2, &[1]), Err(GraphError::WrongAttributeCount { expected: 2, found: 1 })));
+        assert!(matches!(builder.edge(2, 2, 1), Err(GraphError::SelfLoop { vertex: 2 })));
+        assert!(matches!(builder.edge(0, 1, 9), Err(GraphError::DuplicateEdge { origin: 0, destination: 1 })));
+
+        let gr = builder.build();
+        assert_eq!(gr.num_edges, 2);
+        assert_eq!(gr.edges().collect::<Vec<_>>(), vec![(0, 1, 5), (1, 2, 4)]);
+    }
+
+    #[test]
+    fn nomes_sao_validados() {
+        let mut builder: GraphBuilder = GraphBuilder::new(2).undirected().one_based();
+        builder.label(0, "Centro").unwrap().label(0, "Centro").unwrap();
+
+        assert!(matches!(builder.label(1, "Centro"), Err(GraphError::DuplicateLabel(nome)) if nome == "Centro"));
+        assert!(matches!(builder.label(2, "Norte"), Err(GraphError::VertexOutOfRange { vertex: 2, num_vertex: 2 })));
+
+        let gr = builder.build();
+        assert_eq!((gr.is_directed, gr.is_zero_based), (false, false));
+        assert_eq!((gr.label(0), gr.label(1)), (Some("Centro"), None));
+    }
+}
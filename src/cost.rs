@@ -0,0 +1,82 @@
+//! # Escolha do custo minimizado nas arestas com vários atributos
+//! ## Conteúdo:
+//! Qual atributo (ou combinação de atributos) é o custo (Cost);\
+//! Visão do grafo que usa esse custo como peso (CostView).
+//!
+//! A mesma malha viária pode ter distância, tempo e energia em cada aresta. Em vez de um arquivo por atributo, os algoritmos recebem uma visão do grafo em que o peso de cada aresta é o custo escolhido:
+//! ```rust
+//!    let energia = gr.with_cost(Cost::by_name(&gr, "energy").unwrap());
+//!    let (distancias, anteriores) = bellman_ford(&energia, &0);
+//!
+//!    // 70% tempo + 30% energia
+//!    let misto = gr.with_cost(Cost::Combination(vec![(1, 0.7), (2, 0.3)]));
+//!    let (distancias, anteriores) = dijikstra(&misto, &0);
+//! ```
+
+use std::ops::Mul;
+
+pub use crate::graph::{Graph, Node};
+pub use crate::graph_trait::WeightedGraph;
+pub use crate::weight::Weight;
+
+/// # Representa o custo minimizado pelos algoritmos.
+/// ## Variantes
+/// "Attribute" - Um único atributo, pelo índice em Graph::attribute_names (0 é o peso);\
+/// "Combination" - Soma ponderada de atributos, em pares (índice do atributo, coeficiente).
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cost<W> {
+    Attribute(usize),
+    Combination(Vec<(usize, W)>),
+}
+
+impl<W: Weight + Mul<Output = W>> Cost<W> {
+
+    /// # Retorna o custo de um único atributo, procurado pelo nome
+
+    pub fn by_name(grafo: &Graph<W>, nome: &str) -> Option<Self> {
+        grafo.attribute_index(nome).map(Cost::Attribute)
+    }
+
+    /// # Calcula o custo de uma aresta (atributos inexistentes valem zero)
+
+    pub fn evaluate(&self, node: &Node<W>) -> W {
+        match self {
+            Cost::Attribute(i) => node.attribute(*i).unwrap_or(W::zero()),
+            Cost::Combination(termos) => termos
+                .iter()
+                .fold(W::zero(), |soma, (i, coeficiente)| soma + *coeficiente * node.attribute(*i).unwrap_or(W::zero())),
+        }
+    }
+}
+
+/// # Visão do grafo em que o peso de cada aresta é o custo escolhido.
+/// ## Atributos
+/// "grafo" - O grafo original (não é copiado);\
+/// "custo" - O custo usado como peso.
+
+#[derive(Debug)]
+pub struct CostView<'a, W> {
+    pub grafo: &'a Graph<W>,
+    pub custo: Cost<W>,
+}
+
+impl<W: Weight + Mul<Output = W>> Graph<W> {
+
+    /// # Retorna uma visão do grafo que usa o custo escolhido como peso, pronta para os algoritmos
+
+    pub fn with_cost(&self, custo: Cost<W>) -> CostView<'_, W> {
+        CostView { grafo: self, custo }
+    }
+}
+
+impl<W: Weight + Mul<Output = W>> WeightedGraph<W> for CostView<'_, W> {
+    fn num_vertex(&self) -> usize {
+        self.grafo.num_vertex
+    }
+
+    fn neighbors_with_weights(&self, vertice: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        let head = self.grafo.adj.get(&vertice).and_then(|lista| lista.as_deref());
+        std::iter::successors(head, |node| node.next.as_deref()).map(|node| (node.value, self.custo.evaluate(node)))
+    }
+}
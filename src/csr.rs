@@ -0,0 +1,218 @@
+//! # Representação "Compressed Sparse Row" (CSR) do grafo
+//! ## Conteúdo:
+//! Estrutura do grafo congelado (CsrGraph);\
+//! Construção a partir do grafo com lista de adjacência (CsrGraph::from_graph());\
+//! As mesmas consultas do Graph (vizinhos(), weight(), label()).
+//!
+//! Depois de lido, o grafo raramente muda, então vale a pena "congelar" a lista de adjacência em três vetores contíguos:\
+//! "offsets" - Onde começa a lista de vizinhos de cada vértice;\
+//! "targets" - Os vizinhos de todos os vértices, um após o outro;\
+//! "weights" - O peso de cada aresta, na mesma posição do vizinho em "targets";\
+//! "extra" - Os demais atributos, um vetor por atributo, também alinhados com "targets".
+//!
+//! Os vizinhos do vértice v ficam em targets[offsets[v]..offsets[v + 1]], o que deixa a iteração amigável para o cache em grids com milhões de células.
+
+pub use crate::graph::Graph;
+pub use crate::labels::Labels;
+pub use crate::weight::Weight;
+
+use crate::graph::formatar_atributos;
+
+/// # Representa o grafo congelado no formato CSR.
+/// ## Atributos
+/// "is_zero_based" - Copiado do grafo original, para mostrar os vértices como no arquivo;\
+/// "is_directed" - Copiado do grafo original. Em grafos não-direcionados cada aresta aparece nas listas das duas pontas, como no Graph;\
+/// "num_vertex" - Valor da quatidade de vértices do grafo original;\
+/// "offsets" - Vetor de tamanho (linhas + 1) com o início da lista de vizinhos de cada vértice;\
+/// "targets" - Vetor com os vizinhos de todos os vértices;\
+/// "weights" - Vetor com os pesos das arestas, alinhado com "targets";\
+/// "extra" - Um vetor para cada atributo depois do peso (attribute_names[1..]), alinhado com "targets";\
+/// "labels" - Os nomes dos vértices do grafo original;\
+/// "attribute_names" - Nome de cada atributo das arestas, o primeiro é o do peso.
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CsrGraph<W = i32> {
+    pub is_zero_based: bool,
+    pub is_directed: bool,
+    pub num_vertex: usize,
+    pub offsets: Vec<usize>,
+    pub targets: Vec<usize>,
+    pub weights: Vec<W>,
+    pub extra: Vec<Vec<W>>,
+    pub labels: Option<Labels>,
+    pub attribute_names: Vec<String>,
+}
+
+impl<W: Weight> CsrGraph<W> {
+
+    /// # Congela um grafo com lista de adjacência no formato CSR.
+    /// A ordem dos vizinhos de cada vértice é a mesma da lista encadeada original, e todos os atributos, os nomes e a direção são copiados.
+    /// ## Construção
+    /// ```rust
+    ///    // 1: conta o grau de saída de cada vértice
+    ///    offsets[origem + 1] += 1;
+    ///    // 2: soma acumulada, offsets[v] passa a ser o início dos vizinhos de v
+    ///    offsets[v + 1] += offsets[v];
+    ///    // 3: copia cada aresta para a posição livre da sua origem
+    ///    targets[posicao[origem]] = node.value;
+    /// ```
+
+    pub fn from_graph(grafo: &Graph<W>) -> Self {
+        // Número de linhas: cobre tanto o num_vertex quanto qualquer vértice que aparecer nas arestas
+        let mut linhas = grafo.num_vertex;
+        for (origem, lista) in &grafo.adj {
+            linhas = linhas.max(origem + 1);
+            let mut head = lista.as_ref();
+            while let Some(node) = head {
+                linhas = linhas.max(node.value + 1);
+                head = node.next.as_ref();
+            }
+        }
+
+        // Passo 1: grau de saída de cada vértice
+        let mut offsets = vec![0; linhas + 1];
+        for (origem, lista) in &grafo.adj {
+            let mut head = lista.as_ref();
+            while let Some(node) = head {
+                offsets[origem + 1] += 1;
+                head = node.next.as_ref();
+            }
+        }
+
+        // Passo 2: soma acumulada
+        for v in 0..linhas {
+            offsets[v + 1] += offsets[v];
+        }
+
+        // Passo 3: preenchimento, cada origem escreve a partir do seu offset
+        let num_arestas = offsets[linhas];
+        let mut targets = vec![0; num_arestas];
+        let mut weights = vec![W::zero(); num_arestas];
+        let mut extra = vec![vec![W::zero(); num_arestas]; grafo.attribute_names.len() - 1];
+        let mut posicao = offsets.clone();
+        for (origem, lista) in &grafo.adj {
+            let mut head = lista.as_ref();
+            while let Some(node) = head {
+                targets[posicao[*origem]] = node.value;
+                weights[posicao[*origem]] = node.weight;
+                for (a, coluna) in extra.iter_mut().enumerate() {
+                    coluna[posicao[*origem]] = node.attribute(a + 1).unwrap_or(W::zero());
+                }
+                posicao[*origem] += 1;
+                head = node.next.as_ref();
+            }
+        }
+
+        CsrGraph {
+            is_zero_based: grafo.is_zero_based,
+            is_directed: grafo.is_directed,
+            num_vertex: grafo.num_vertex,
+            offsets,
+            targets,
+            weights,
+            extra,
+            labels: grafo.labels.clone(),
+            attribute_names: grafo.attribute_names.clone(),
+        }
+    }
+
+    /// # Função que retorna o número de vérfices do grafo
+
+    pub fn num_vertex(&self) -> usize {
+        self.num_vertex
+    }
+
+    /// # Função que retorna o número de arestas armazenadas
+
+    pub fn num_edges(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// # Retorna o intervalo de "targets" e "weights" que guarda os vizinhos de um vértice (vazio se o vértice não existir)
+
+    pub(crate) fn intervalo(&self, vertice: usize) -> std::ops::Range<usize> {
+        if vertice + 1 < self.offsets.len() {
+            self.offsets[vertice]..self.offsets[vertice + 1]
+        } else {
+            0..0
+        }
+    }
+
+    /// # Retorna uma lista de vizinhos de um vertice
+
+    pub fn vizinhos(&self, vertice: &usize) -> Vec<usize> {
+        self.targets[self.intervalo(*vertice)].to_vec()
+    }
+
+    /// # Retorna o peso de uma aresta
+
+    pub fn weight(&self, origem: &usize, destino: &usize) -> Option<W> {
+        let intervalo = self.intervalo(*origem);
+        self.targets[intervalo.clone()]
+            .iter()
+            .position(|v| v == destino)
+            .map(|i| self.weights[intervalo.start + i])
+    }
+
+    /// # Retorna o nome do vértice, se existir
+
+    pub fn label(&self, vertice: usize) -> Option<&str> {
+        self.labels.as_ref()?.name(vertice)
+    }
+
+    /// # Retorna o nome do vértice, ou o número dele na indexação original, igual ao Graph::vertex_name()
+
+    pub fn vertex_name(&self, vertice: usize) -> String {
+        match self.label(vertice) {
+            Some(nome) => nome.to_string(),
+            None if self.is_zero_based => vertice.to_string(),
+            None => (vertice + 1).to_string(),
+        }
+    }
+
+    /// # Função para printar o grafo, no mesmo formato do Graph::print()
+
+    pub fn print(&self) {
+        for v in 0..self.offsets.len() - 1 {
+            let intervalo = self.intervalo(v);
+            if intervalo.is_empty() {
+                continue;
+            }
+            print!("{} -> ", self.vertex_name(v));
+            let vizinhos: Vec<String> = intervalo
+                .map(|i| format!("{} ({})", self.vertex_name(self.targets[i]), self.format_attributes(i)))
+                .collect();
+            println!("{}", vizinhos.join(" -> "));
+        }
+    }
+
+    /// # Formata todos os atributos da aresta na posição i de "targets", igual ao Graph::print()
+
+    fn format_attributes(&self, i: usize) -> String {
+        formatar_atributos(&self.attribute_names, |a| if a == 0 { self.weights[i] } else { self.extra[a - 1][i] })
+    }
+}
+
+impl<W: Weight> From<&Graph<W>> for CsrGraph<W> {
+    fn from(grafo: &Graph<W>) -> Self {
+        CsrGraph::from_graph(grafo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arestas_mostram_todos_os_atributos_como_no_graph() {
+        let mut gr: Graph = Graph::with_attributes(false, 3, &["distance", "time"]);
+        gr.edge_with_attributes(0, 1, &[5, 3]).unwrap();
+        gr.edge_with_attributes(0, 2, &[7, 1]).unwrap();
+        gr.set_label(2, "Sé");
+        let csr = CsrGraph::from_graph(&gr);
+        assert_eq!(csr.format_attributes(0), "distance: 5, time: 3");
+        assert_eq!(csr.format_attributes(1), "distance: 7, time: 1");
+        assert_eq!((csr.vertex_name(1), csr.vertex_name(2)), ("2".to_string(), "Sé".to_string()));
+    }
+}
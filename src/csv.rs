@@ -0,0 +1,286 @@
+//! # Leitura de listas de arestas em CSV
+//! ## Conteúdo:
+//! Opções de leitura, com o nome das colunas usadas (CsvOptions);\
+//! Leitura do CSV para o Graph (read_csv()).
+//!
+//! As exportações da rede vêm como CSV com cabeçalho, por exemplo:
+//! ```rust
+//!    from,to,energy_wh,length_m
+//!    "Estação A","Estação B",120,850
+//! ```
+//! O chamador diz qual coluna é a origem, qual é o destino e quais viram atributos das arestas:
+//! ```rust
+//!    let options = CsvOptions { source: "from".into(), target: "to".into(), weights: vec!["energy_wh".into(), "length_m".into()], remap_ids: true, ..Default::default() };
+//!    let gr: Graph = read_csv("data/rede.csv", &options)?;
+//! ```
+
+pub use crate::error::{GraphError, ParseErrorKind};
+pub use crate::graph::{Graph, HashMap, Indexing, ParallelEdges, fs};
+pub use crate::weight::Weight;
+
+use crate::graph::{nome_de_atributo_valido, parse_campo};
+
+/// # Opções de leitura do CSV.
+/// ## Atributos
+/// "source" e "target" - Nome das colunas de origem e destino ("from" e "to" por padrão);\
+/// "weights" - Nome das colunas que viram atributos das arestas, a primeira é o peso (["weight"] por padrão, precisa ter ao menos uma e os nomes não podem ter espaços);\
+/// "delimiter" - Separador das colunas (',' por padrão);\
+/// "remap_ids" - Se true, os ids podem ser qualquer texto: cada id novo vira o próximo vértice (0, 1, 2...) e o id original vira o nome do vértice;\
+/// "indexing" - Com remap_ids false, como os ids numéricos estão numerados (Auto por padrão, como no read_graph()). Indexing::Remap é o mesmo que remap_ids true;\
+/// "is_directed" - Se as arestas têm direção (true por padrão);\
+/// "parallel_edges" - Política para arestas repetidas (KeepAll por padrão).
+///
+/// As demais colunas do arquivo são ignoradas.
+
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    pub source: String,
+    pub target: String,
+    pub weights: Vec<String>,
+    pub delimiter: char,
+    pub remap_ids: bool,
+    pub indexing: Indexing,
+    pub is_directed: bool,
+    pub parallel_edges: ParallelEdges,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            source: "from".to_string(),
+            target: "to".to_string(),
+            weights: vec!["weight".to_string()],
+            delimiter: ',',
+            remap_ids: false,
+            indexing: Indexing::Auto,
+            is_directed: true,
+            parallel_edges: ParallelEdges::KeepAll,
+        }
+    }
+}
+
+/// # Função que lê um grafo de uma lista de arestas em CSV.
+/// ## Argumentos
+///
+/// "path" - O caminho do arquivo, com o cabeçalho na primeira linha;\
+/// "options" - As colunas usadas e como tratar os ids.
+///
+/// ## Retorno
+///
+/// O grafo (os atributos das arestas têm o nome das colunas) ou o GraphError com a linha e a coluna do problema.
+/// ## Quantidade de vértices
+/// Com remap_ids é a quantidade de ids diferentes. Sem remap_ids é o maior id (mais 1 se o arquivo for 0-based), já que o CSV não tem um cabeçalho com o número de vértices.
+/// ## Aspas
+/// Campos entre aspas podem ter o separador e aspas duplicadas ("") dentro deles, mas não quebras de linha. Linhas em branco são ignoradas.
+
+pub fn read_csv<W: Weight>(path: &str, options: &CsvOptions) -> Result<Graph<W>, GraphError> {
+    assert!(!options.weights.is_empty(), "At least one weight column is required.");
+    let content = fs::read_to_string(path).map_err(|e| GraphError::io(path, e))?;
+
+    let mut linhas = content
+        .lines()
+        .enumerate()
+        .filter(|(_, linha)| !linha.trim().is_empty())
+        .map(|(i, linha)| (i + 1, campos_com_coluna(linha, options.delimiter)));
+
+    let (linha_cabecalho, cabecalho) = linhas.next().ok_or_else(|| GraphError::parse(path, 1, 1, ParseErrorKind::EmptyFile))?;
+    let coluna = |nome: &str| {
+        cabecalho
+            .iter()
+            .position(|(_, campo)| campo.trim() == nome)
+            .ok_or_else(|| GraphError::parse(path, linha_cabecalho, 1, ParseErrorKind::MissingColumn(nome.to_string())))
+    };
+    let coluna_origem = coluna(&options.source)?;
+    let coluna_destino = coluna(&options.target)?;
+    let colunas_pesos = options.weights.iter().map(|nome| coluna(nome)).collect::<Result<Vec<usize>, GraphError>>()?;
+    // Os nomes das colunas viram os atributos, e a linha "attrs" do write_graph() é separada por espaços
+    for (nome, &indice) in options.weights.iter().zip(&colunas_pesos) {
+        if !nome_de_atributo_valido(nome) {
+            return Err(GraphError::parse(path, linha_cabecalho, cabecalho[indice].0, ParseErrorKind::InvalidAttributeName(nome.to_string())));
+        }
+    }
+
+    // Com remap_ids cada id novo recebe o próximo vértice, na ordem em que aparece
+    let remap_ids = options.remap_ids || options.indexing == Indexing::Remap;
+    let mut ids: HashMap<String, usize> = HashMap::new();
+    let mut nomes: Vec<String> = Vec::new();
+    let mut arestas = Vec::new();
+
+    for (linha, campos) in linhas {
+        if campos.len() != cabecalho.len() {
+            let coluna = campos.get(cabecalho.len()).or(campos.last()).map_or(1, |(c, _)| *c);
+            return Err(GraphError::parse(path, linha, coluna, ParseErrorKind::WrongColumnCount { expected: cabecalho.len(), found: campos.len() }));
+        }
+
+        let mut vertices = [(0, 0); 2];
+        for (i, indice) in [coluna_origem, coluna_destino].into_iter().enumerate() {
+            let (col, campo) = &campos[indice];
+            let campo = campo.trim();
+            vertices[i] = if remap_ids {
+                let proximo = ids.len();
+                let vertice = *ids.entry(campo.to_string()).or_insert_with(|| {
+                    nomes.push(campo.to_string());
+                    proximo
+                });
+                (*col, vertice)
+            } else {
+                (*col, parse_campo::<usize>(path, linha, (*col, campo), "um vértice")?)
+            };
+        }
+
+        let valores = colunas_pesos
+            .iter()
+            .map(|&indice| parse_campo::<W>(path, linha, (campos[indice].0, campos[indice].1.trim()), "um peso"))
+            .collect::<Result<Vec<W>, GraphError>>()?;
+
+        arestas.push((linha, vertices[0], vertices[1], valores));
+    }
+
+    // Sem remap_ids a indexação e a quantidade de vértices vêm dos próprios ids
+    let (is_zero_based, num_vertex) = if remap_ids {
+        (true, nomes.len())
+    } else {
+        let maior = arestas.iter().map(|(_, (_, origem), (_, destino), _)| *origem.max(destino)).max();
+        let tem_zero = arestas.iter().any(|(_, (_, origem), (_, destino), _)| *origem == 0 || *destino == 0);
+        let is_zero_based = match options.indexing {
+            Indexing::ZeroBased | Indexing::Remap => true,
+            Indexing::OneBased => false,
+            Indexing::Auto => tem_zero,
+        };
+        if !is_zero_based {
+            let zero = arestas
+                .iter()
+                .flat_map(|(linha, origem, destino, _)| [(*linha, *origem), (*linha, *destino)])
+                .find(|(_, (_, vertice))| *vertice == 0);
+            if let Some((linha, (coluna, _))) = zero {
+                return Err(GraphError::parse(path, linha, coluna, ParseErrorKind::VertexOutOfRange { vertex: 0, num_vertex: maior.unwrap_or(0), is_zero_based: false }));
+            }
+        }
+        (is_zero_based, maior.map_or(0, |maior| if is_zero_based { maior + 1 } else { maior }))
+    };
+
+    let atributos: Vec<&str> = options.weights.iter().map(|nome| nome.as_str()).collect();
+    let mut graph = Graph::with_attributes(is_zero_based, num_vertex, &atributos);
+    graph.is_directed = options.is_directed;
+    graph.parallel_edges = options.parallel_edges;
+
+    let ajuste = if is_zero_based { 0 } else { 1 };
+    for (linha, (coluna, origem), (_, destino), valores) in arestas {
+        graph.edge_na_leitura(origem - ajuste, destino - ajuste, &valores).map_err(|_| {
+            GraphError::parse(path, linha, coluna, ParseErrorKind::DuplicateEdge { origin: origem, destination: destino })
+        })?;
+    }
+    graph.terminar_leitura();
+
+    for (vertice, nome) in nomes.iter().enumerate() {
+        graph.set_label(vertice, nome);
+    }
+
+    Ok(graph)
+}
+
+/// # Separa uma linha do CSV em campos, guardando a coluna (1-based, em caracteres) onde cada um começa.
+/// Campos entre aspas perdem as aspas, e "" dentro deles vira uma aspa.
+
+fn campos_com_coluna(linha: &str, separador: char) -> Vec<(usize, String)> {
+    let mut campos = Vec::new();
+    let mut atual = String::new();
+    let mut inicio = 1;
+    let mut entre_aspas = false;
+    let mut chars = linha.chars().enumerate().peekable();
+
+    while let Some((coluna, ch)) = chars.next() {
+        match ch {
+            '"' if entre_aspas => {
+                if chars.peek().is_some_and(|(_, proximo)| *proximo == '"') {
+                    atual.push('"');
+                    chars.next();
+                } else {
+                    entre_aspas = false;
+                }
+            }
+            '"' if atual.trim().is_empty() => {
+                atual.clear();
+                entre_aspas = true;
+            }
+            c if c == separador && !entre_aspas => {
+                campos.push((inicio, std::mem::take(&mut atual)));
+                inicio = coluna + 2;
+            }
+            c => atual.push(c),
+        }
+    }
+    campos.push((inicio, atual));
+    campos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::tests::{arestas_com_atributos, arquivo_temporario};
+    use crate::graph::{ReadOptions, read_graph_from, write_graph_to};
+
+    fn erro(nome: &str, conteudo: &str, options: &CsvOptions) -> (usize, usize, ParseErrorKind) {
+        match read_csv::<i32>(&arquivo_temporario(nome, conteudo.as_bytes()), options) {
+            Err(GraphError::Parse(erro)) => (erro.line, erro.column, erro.kind),
+            outro => panic!("esperado um erro de formatação, veio {:?}", outro.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn ids_entre_aspas_viram_nomes_e_voltam_pelo_txt() {
+        let conteudo = "from,to,energy_wh,length_m\n\"Estação A\",\"Estação B\",120,850\n\n\"Estação B\",\"Rua \"\"X\"\", 10\",5,30\n\"Estação A\",\"Rua \"\"X\"\", 10\",7,40\n";
+        let options = CsvOptions { weights: vec!["energy_wh".to_string(), "length_m".to_string()], remap_ids: true, ..Default::default() };
+        let gr: Graph = read_csv(&arquivo_temporario("aspas.csv", conteudo.as_bytes()), &options).unwrap();
+
+        assert_eq!(gr.num_vertex, 3);
+        assert_eq!([0, 1, 2].map(|v| gr.label(v)), [Some("Estação A"), Some("Estação B"), Some("Rua \"X\", 10")]);
+        assert_eq!(gr.attribute_names, vec!["energy_wh".to_string(), "length_m".to_string()]);
+        assert_eq!(arestas_com_atributos(&gr), vec![(0, 1, vec![120, 850]), (0, 2, vec![7, 40]), (1, 2, vec![5, 30])]);
+
+        let mut texto: Vec<u8> = Vec::new();
+        write_graph_to(&gr, &mut texto).unwrap();
+        let lido: Graph = read_graph_from(texto.as_slice(), "rede.txt", &ReadOptions::default()).unwrap();
+        assert_eq!(arestas_com_atributos(&lido), arestas_com_atributos(&gr));
+        assert_eq!([0, 1, 2].map(|v| lido.label(v)), [0, 1, 2].map(|v| gr.label(v)));
+    }
+
+    #[test]
+    fn ids_numericos_seguem_a_indexacao() {
+        let conteudo = "weight;to;from\n5;2;1\n7;3;2\n";
+        let options = CsvOptions { delimiter: ';', ..Default::default() };
+        let gr: Graph = read_csv(&arquivo_temporario("um.csv", conteudo.as_bytes()), &options).unwrap();
+        assert!(!gr.is_zero_based);
+        assert_eq!(gr.num_vertex, 3);
+        assert_eq!(gr.edges().collect::<Vec<_>>(), vec![(0, 1, 5), (1, 2, 7)]);
+
+        let options = CsvOptions { indexing: Indexing::OneBased, ..Default::default() };
+        assert_eq!(erro("zero.csv", "from,to,weight\n1,2,5\n2,0,7\n", &options), (3, 3, ParseErrorKind::VertexOutOfRange { vertex: 0, num_vertex: 2, is_zero_based: false }));
+    }
+
+    #[test]
+    fn erros_apontam_a_linha_e_a_coluna() {
+        let options = CsvOptions::default();
+        assert_eq!(erro("vazio.csv", "\n\n", &options), (1, 1, ParseErrorKind::EmptyFile));
+        assert_eq!(erro("coluna.csv", "from,to,peso\n1,2,5\n", &options), (1, 1, ParseErrorKind::MissingColumn("weight".to_string())));
+        assert_eq!(erro("colunas.csv", "from,to,weight\n1,2,5,9\n", &options), (2, 7, ParseErrorKind::WrongColumnCount { expected: 3, found: 4 }));
+        assert_eq!(erro("peso.csv", "from,to,weight\n1,2,5\n2, 3,\"1O\"\n", &options), (3, 6, ParseErrorKind::InvalidToken { token: "1O".to_string(), expected: "um peso" }));
+        assert_eq!(erro("vertice.csv", "from,to,weight\nA,2,5\n", &options), (2, 1, ParseErrorKind::InvalidToken { token: "A".to_string(), expected: "um vértice" }));
+
+        let options = CsvOptions { parallel_edges: ParallelEdges::Reject, ..Default::default() };
+        assert_eq!(erro("repetida.csv", "from,to,weight\n1,2,5\n2,3,1\n1,2,7\n", &options), (4, 1, ParseErrorKind::DuplicateEdge { origin: 1, destination: 2 }));
+    }
+
+    #[test]
+    fn coluna_de_peso_com_espaco_e_recusada_no_cabecalho() {
+        let path = arquivo_temporario("espaco.csv", b"from,to,energy wh\n1,2,5\n");
+        let options = CsvOptions { weights: vec!["energy wh".to_string()], ..Default::default() };
+        match read_csv::<i32>(&path, &options) {
+            Err(GraphError::Parse(erro)) => {
+                assert_eq!((erro.line, erro.column), (1, 9));
+                assert_eq!(erro.kind, ParseErrorKind::InvalidAttributeName("energy wh".to_string()));
+            }
+            outro => panic!("esperado um erro de formatação, veio {:?}", outro.map(|_| ())),
+        }
+    }
+}
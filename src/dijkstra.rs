@@ -5,17 +5,20 @@
 //! Função de backtracking (reconstruir_caminho());\
 //! Associação com o livro (dijikstra())
 
+#![allow(non_snake_case)] // A, F e S seguem a notação do livro
+
 pub use crate::graph::Graph; 
-pub use std::cmp;
+pub use crate::graph_trait::WeightedGraph;
+pub use crate::weight::Weight;
 
-/// # Retorna a cópia do vértice mais próximo.
-pub fn vertice_mais_proximo(distancias: &Vec<i32>, A: &Vec<usize>) -> Option<usize>{
-    let mut distancia_minima = i32::MAX;
+/// # Retorna a cópia do vértice mais próximo (o primeiro de A, em caso de empate).
+pub fn vertice_mais_proximo<W: Weight>(distancias: &[W], A: &[usize]) -> Option<usize>{
+    let mut distancia_minima: Option<W> = None;
     let mut escolhido = None;
 
     for v in A{
-        if distancias[*v] < distancia_minima{
-            distancia_minima = distancias[*v];
+        if distancia_minima.is_none_or(|d| distancias[*v] < d){
+            distancia_minima = Some(distancias[*v]);
             escolhido = Some(v);
         }
     }
@@ -23,16 +26,14 @@ pub fn vertice_mais_proximo(distancias: &Vec<i32>, A: &Vec<usize>) -> Option<usi
     escolhido.copied()
 }
 
-/// # Retorna um vetor com a interseção dos vértices em A e os vizinhos de um certo vértice.
-pub fn intersecao(A: &Vec<usize>, grafo: &Graph, vertice: &usize) -> Vec<usize> { // O(n) tem como deixar O(1) transformando A em hashset
-    let mut intersec = grafo.vizinhos(vertice);
-    intersec.retain(|x| A.contains(x));
-
-    intersec
+/// # Retorna a interseção dos vértices em A e os vizinhos de um certo vértice, já com o peso de cada aresta.
+/// "em_A" marca os vértices que estão em A, então o teste é O(1) e nada é alocado. Como o peso vem junto, não é preciso procurar a aresta de novo com weight().
+pub fn intersecao<'a, W: Weight, G: WeightedGraph<W>>(em_A: &'a [bool], grafo: &'a G, vertice: usize) -> impl Iterator<Item = (usize, W)> + 'a {
+    grafo.neighbors_with_weights(vertice).filter(move |(vizinho, _)| em_A[*vizinho])
 }
 
 /// # Retorna um vetor com o caminho pra o destino <- acho melhor botar isso num arquivo rs de utilidade
-pub fn reconstruir_caminho(origem: usize, destino: usize, anterior: &Vec<Option<usize>>) -> Vec<usize>{
+pub fn reconstruir_caminho(origem: usize, destino: usize, anterior: &[Option<usize>]) -> Vec<usize>{
     let mut caminho = vec![];
     let mut atual = Some(destino);
 
@@ -51,7 +52,7 @@ pub fn reconstruir_caminho(origem: usize, destino: usize, anterior: &Vec<Option<
 /// # Algoritmo e associação com o livro.
 /// ## Argumentos
 /// 
-/// "grafo" - O grafo analisado;\
+/// "grafo" - O grafo analisado (qualquer representação que implemente WeightedGraph);\
 /// "origem" - Vértice origem do caminho.
 ///
 /// ## Retorno
@@ -59,16 +60,21 @@ pub fn reconstruir_caminho(origem: usize, destino: usize, anterior: &Vec<Option<
 /// Vetor de custos para os demais vértices;\
 /// Vetor de anteriores, para a reconstruçao do caminho.
 /// 
+/// ## Desempate
+/// Com duas distâncias iguais em A, vertice_mais_proximo() escolhe o vértice de menor índice (A fica sempre em ordem crescente).\
+/// Um anterior só é trocado com uma distância estritamente menor, então entre dois caminhos de mesmo custo fica o anterior que foi fechado primeiro e, no mesmo vértice, a aresta incluída primeiro.
+/// 
 /// ## Associação com livro
 /// ```rust
-/// let num_v = grafo.num_vertex() + 1;
+/// let num_v = grafo.num_vertex();
 ///
 ///   // Linha 1: Cria um vetor de distâncias, inicializando todos com INFINITO. Em seguida a distancia com a origem recebe zero
-///    let mut d = vec![i32::MAX/2; num_v]; //ta dividido por 2 pra evitar overflow
-///    d[*origem] = 0;
+///    let mut d = vec![W::infinity(); num_v];
+///    d[*origem] = W::zero();
 ///
 ///    //Linha 2: Cria o vetor A (aberto) com os vértices e F (fechado) vazio. em seguida cria o vetor anterior para o caminho minimo
 ///    let mut A = grafo.vertices_list();
+///    let mut em_A = vec![true; num_v];
 ///    let mut F = vec![];
 ///    let mut anterior = vec![None; num_v];
 ///
@@ -79,42 +85,45 @@ pub fn reconstruir_caminho(origem: usize, destino: usize, anterior: &Vec<Option<
 ///            Some(v) => v,
 ///            None => break, //Não há vizinhos -> rever o que fazer
 ///        };
+///        if d[r] == W::infinity() { // Os vértices que sobraram em A não são alcançáveis a partir da origem
+///            break;
+///        }
 ///
 ///        //Linha 5: Adiciona r em F e remove r de A
 ///        F.push(r);
 ///        A.retain(|&x| x != r); // mantem apenas itens que não são iguais a r
+///        em_A[r] = false;
 ///
-///        //Linha 6: fazer a interseção dos vizinhos de r e os vertices que estão em A
-///        let S = intersecao(&A, &grafo, &r);
+///        //Linha 6: fazer a interseção dos vizinhos de r e os vertices que estão em A (com o peso v(r,i) de cada aresta)
+///        let S = intersecao(&em_A, grafo, r);
 ///
 ///        //Linha 7 inicio do loop
-///        for i in S {
+///        for (i, peso_ri) in S {
 ///            //Linha 8 p recebe o valor menor entre a distancia(1,i) anterior com [d(1,r) + v(r,i)]
-///            if let Some(peso_ri) = grafo.weight(&r, &i) {
-///                let soma_nova = d[r] + peso_ri;
-///                let p = cmp::min(d[i], soma_nova);
+///            let soma_nova = d[r] + peso_ri;
+///            let p = if soma_nova < d[i] { soma_nova } else { d[i] };
 ///
-///                //Linha 9
-///                if p < d[i] {
-///                    //Linha 10 O valor anterior recebe p e r é inserido no vetor de caminho anterior na posição i
-///                    d[i] = p;
-///                    anterior[i] = Some(r);
-///                }
-///            };    
+///            //Linha 9
+///            if p < d[i] {
+///                //Linha 10 O valor anterior recebe p e r é inserido no vetor de caminho anterior na posição i
+///                d[i] = p;
+///                anterior[i] = Some(r);
+///            }
 ///        }
 ///    }
 ///
 ///    (d, anterior) // Retorno
 /// ```
-pub fn dijikstra(grafo: &Graph, origem: &usize) -> (Vec<i32>, Vec<Option<usize>>) { //retorna as distancias e anteriores pros caminhos
-    let num_v = grafo.num_vertex() + 1;
+pub fn dijikstra<W: Weight, G: WeightedGraph<W>>(grafo: &G, origem: &usize) -> (Vec<W>, Vec<Option<usize>>) { //retorna as distancias e anteriores pros caminhos
+    let num_v = grafo.num_vertex();
 
     // Linha 1: Cria um vetor de distâncias, inicializando todos com INFINITO. Em seguida a distancia com a origem recebe zero
-    let mut d = vec![i32::MAX/2; num_v]; //ta dividido por 2 pra evitar overflow
-    d[*origem] = 0;
+    let mut d = vec![W::infinity(); num_v];
+    d[*origem] = W::zero();
 
     //Linha 2: Cria o vetor A (aberto) com os vértices e F (fechado) vazio. em seguida cria o vetor anterior para o caminho minimo
     let mut A = grafo.vertices_list();
+    let mut em_A = vec![true; num_v];
     let mut F = vec![];
     let mut anterior = vec![None; num_v];
 
@@ -125,28 +134,30 @@ pub fn dijikstra(grafo: &Graph, origem: &usize) -> (Vec<i32>, Vec<Option<usize>>
             Some(v) => v,
             None => break, //Não há vizinhos -> rever o que fazer
         };
+        if d[r] == W::infinity() { // Os vértices que sobraram em A não são alcançáveis a partir da origem
+            break;
+        }
 
         //Linha 5: Adiciona r em F e remove r de A
         F.push(r);
         A.retain(|&x| x != r); // mantem apenas itens que não são iguais a r
+        em_A[r] = false;
 
-        //Linha 6: fazer a interseção dos vizinhos de r e os vertices que estão em A
-        let S = intersecao(&A, &grafo, &r);
+        //Linha 6: fazer a interseção dos vizinhos de r e os vertices que estão em A (com o peso v(r,i) de cada aresta)
+        let S = intersecao(&em_A, grafo, r);
 
         //Linha 7 inicio do loop
-        for i in S {
+        for (i, peso_ri) in S {
             //Linha 8 p recebe o valor menor entre a distancia(1,i) anterior com [d(1,r) + v(r,i)]
-            if let Some(peso_ri) = grafo.weight(&r, &i) {
-                let soma_nova = d[r] + peso_ri;
-                let p = cmp::min(d[i], soma_nova);
-
-                //Linha 9
-                if p < d[i] {
-                    //Linha 10 O valor anterior recebe p e r é inserido no vetor de caminho anterior na posição i
-                    d[i] = p;
-                    anterior[i] = Some(r);
-                }
-            };    
+            let soma_nova = d[r] + peso_ri;
+            let p = if soma_nova < d[i] { soma_nova } else { d[i] };
+
+            //Linha 9
+            if p < d[i] {
+                //Linha 10 O valor anterior recebe p e r é inserido no vetor de caminho anterior na posição i
+                d[i] = p;
+                anterior[i] = Some(r);
+            }
         }
     }
 
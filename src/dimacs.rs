@@ -0,0 +1,179 @@
+//! # Leitura e escrita do formato DIMACS de caminho mínimo (.gr)
+//! ## Conteúdo:
+//! Leitura de um arquivo .gr para o Graph (read_dimacs());\
+//! Escrita do Graph em um arquivo .gr (write_dimacs() e write_dimacs_to()).
+//!
+//! As malhas viárias do "9th DIMACS Implementation Challenge" usam esse formato, sempre 1-based e direcionado:
+//! ```rust
+//!    c comentário qualquer          // linhas "c" são ignoradas
+//!    p sp <num_vertices> <num_arcos> // uma única vez, antes dos arcos
+//!    a <vertice_inicial> <vertice_final> <custo>
+//! ```
+//! Assim os algoritmos rodam direto nas instâncias de benchmark:
+//! ```rust
+//!    let gr: Graph = read_dimacs("data/USA-road-d.NY.gr")?;
+//!    let (distancias, anteriores) = dijikstra(&gr, &0);
+//! ```
+
+pub use crate::error::{GraphError, ParseErrorKind};
+pub use crate::graph::{Graph, BufRead, BufReader, BufWriter, File, Write};
+pub use crate::weight::Weight;
+
+use crate::graph::{ajustar_vertice, checar_colunas, parse_campo, tokens_com_coluna};
+use std::io;
+
+/// # Função que lê um grafo no formato DIMACS.
+/// ## Argumentos
+///
+/// "path" - O caminho do arquivo .gr.
+///
+/// ## Retorno
+///
+/// O grafo direcionado e 1-based (os vértices ficam 0-based no BTreeMap, como no read_graph()), ou o GraphError com a linha e a coluna do problema.
+/// ## Erros
+/// A leitura é estrita como a do read_graph(): arco antes da linha "p", linha "p" repetida ou que não seja "sp", tipo de linha desconhecido, token inválido, vértice fora do intervalo e diferença entre a quantidade de arcos da linha "p" e do arquivo.
+
+pub fn read_dimacs<W: Weight>(path: &str) -> Result<Graph<W>, GraphError> {
+    let file = File::open(path).map_err(|e| GraphError::io(path, e))?;
+    let reader = BufReader::new(file);
+
+    let mut grafo: Option<Graph<W>> = None;
+    let mut cabecalho = (0, 0, 0); // (linha, coluna do número de arcos, número de arcos)
+    let mut num_arcos = 0;
+
+    for (i, conteudo) in reader.lines().enumerate() {
+        let linha = i + 1;
+        let conteudo = conteudo.map_err(|e| GraphError::io(path, e))?;
+        let tokens = tokens_com_coluna(&conteudo);
+
+        match tokens.first() {
+            None | Some((_, "c")) => continue,
+            Some((_, "p")) if grafo.is_none() => {
+                checar_colunas(path, linha, &tokens, 4)?;
+                if tokens[1].1 != "sp" {
+                    return Err(GraphError::parse(path, linha, tokens[1].0, ParseErrorKind::InvalidToken { token: tokens[1].1.to_string(), expected: "\"sp\"" }));
+                }
+                let num_vertices = parse_campo::<usize>(path, linha, tokens[2], "um número de vértices")?;
+                let arcos = parse_campo::<usize>(path, linha, tokens[3], "um número de arcos")?;
+                cabecalho = (linha, tokens[3].0, arcos);
+                grafo = Some(Graph::new(false, num_vertices));
+            }
+            Some(&(coluna, "a")) => {
+                let Some(grafo) = grafo.as_mut() else {
+                    return Err(GraphError::parse(path, linha, coluna, ParseErrorKind::InvalidToken { token: "a".to_string(), expected: "a linha \"p sp <n> <m>\" antes dos arcos" }));
+                };
+                checar_colunas(path, linha, &tokens, 4)?;
+                let origem_raw = parse_campo::<usize>(path, linha, tokens[1], "um vértice")?;
+                let destino_raw = parse_campo::<usize>(path, linha, tokens[2], "um vértice")?;
+                let custo = parse_campo::<W>(path, linha, tokens[3], "um custo")?;
+                let origem = ajustar_vertice(path, linha, tokens[1].0, origem_raw, grafo.num_vertex, false)?;
+                let destino = ajustar_vertice(path, linha, tokens[2].0, destino_raw, grafo.num_vertex, false)?;
+                grafo.edge_na_leitura(origem, destino, &[custo])?;
+                num_arcos += 1;
+            }
+            Some(&(coluna, token)) => {
+                return Err(GraphError::parse(path, linha, coluna, ParseErrorKind::InvalidToken { token: token.to_string(), expected: "uma linha \"c\", \"p\" ou \"a\"" }));
+            }
+        }
+    }
+
+    let mut grafo = grafo.ok_or_else(|| GraphError::parse(path, 1, 1, ParseErrorKind::EmptyFile))?;
+    let (linha, coluna, arcos) = cabecalho;
+    if num_arcos != arcos {
+        return Err(GraphError::parse(path, linha, coluna, ParseErrorKind::EdgeCountMismatch { header: arcos, found: num_arcos }));
+    }
+    grafo.declared_edges = Some(arcos);
+    grafo.terminar_leitura();
+
+    Ok(grafo)
+}
+
+/// # Função que salva o grafo em um arquivo .gr.
+/// ## Argumentos
+///
+/// "grafo" - O grafo a ser salvo;\
+/// "path" - O caminho do arquivo (sobrescrito se já existir).
+///
+/// ## Retorno
+///
+/// Nada, ou um GraphError::Io com o caminho do arquivo.
+
+pub fn write_dimacs<W: Weight>(grafo: &Graph<W>, path: &str) -> Result<(), GraphError> {
+    let file = File::create(path).map_err(|e| GraphError::io(path, e))?;
+    let mut writer = BufWriter::new(file);
+    write_dimacs_to(grafo, &mut writer).and_then(|_| writer.flush()).map_err(|e| GraphError::io(path, e))
+}
+
+/// # Função que escreve o grafo no formato DIMACS em qualquer destino.
+/// O formato é sempre 1-based, qualquer que seja o is_zero_based do grafo, e só guarda o peso (o primeiro atributo).\
+/// Arestas não-direcionadas viram dois arcos, um em cada sentido (laços viram um arco só).
+
+pub fn write_dimacs_to<W: Weight, O: Write>(grafo: &Graph<W>, writer: &mut O) -> io::Result<()> {
+    let mut arcos: Vec<(usize, usize, W)> = Vec::new();
+    for (origem, lista) in &grafo.adj {
+        let head = lista.as_deref();
+        for node in std::iter::successors(head, |node| node.next.as_deref()) {
+            arcos.push((origem + 1, node.value + 1, node.weight));
+        }
+    }
+
+    writeln!(writer, "c gerado por graph_in_rust")?;
+    writeln!(writer, "p sp {} {}", grafo.num_vertex, arcos.len())?;
+    for (origem, destino, custo) in arcos {
+        writeln!(writer, "a {} {} {}", origem, destino, custo)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::tests::arquivo_temporario;
+
+    fn erro(texto: &str, nome: &str) -> (usize, usize, ParseErrorKind) {
+        match read_dimacs::<i32>(&arquivo_temporario(nome, texto.as_bytes())) {
+            Err(GraphError::Parse(erro)) => (erro.line, erro.column, erro.kind),
+            outro => panic!("esperado um erro de formatação, veio {:?}", outro.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn dimacs_ida_e_volta() {
+        let mut gr: Graph = Graph::new(true, 3);
+        gr.edge(0, 1, 4).unwrap();
+        gr.edge(1, 2, -3).unwrap();
+        gr.edge(2, 0, 9).unwrap();
+        let mut texto: Vec<u8> = Vec::new();
+        write_dimacs_to(&gr, &mut texto).unwrap();
+        assert!(String::from_utf8_lossy(&texto).contains("p sp 3 3\na 1 2 4\n"));
+
+        let lido: Graph = read_dimacs(&arquivo_temporario("ida.gr", &texto)).unwrap();
+        assert_eq!((lido.is_zero_based, lido.is_directed, lido.num_vertex, lido.num_edges), (false, true, 3, 3));
+        assert_eq!(lido.edges().collect::<Vec<_>>(), gr.edges().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn aresta_nao_direcionada_vira_dois_arcos() {
+        let mut gr: Graph = Graph::new(true, 2);
+        gr.is_directed = false;
+        gr.edge(0, 1, 6).unwrap();
+        let mut texto: Vec<u8> = Vec::new();
+        write_dimacs_to(&gr, &mut texto).unwrap();
+
+        let lido: Graph = read_dimacs(&arquivo_temporario("dois_arcos.gr", &texto)).unwrap();
+        assert_eq!(lido.edges().collect::<Vec<_>>(), vec![(0, 1, 6), (1, 0, 6)]);
+    }
+
+    #[test]
+    fn erros_apontam_a_linha_e_a_coluna() {
+        assert_eq!(erro("c sem cabeçalho\na 1 2 3\n", "sem_p.gr"), (2, 1, ParseErrorKind::InvalidToken { token: "a".to_string(), expected: "a linha \"p sp <n> <m>\" antes dos arcos" }));
+        assert_eq!(erro("p max 2 1\n", "max.gr"), (1, 3, ParseErrorKind::InvalidToken { token: "max".to_string(), expected: "\"sp\"" }));
+        assert_eq!(erro("p sp 2 1\na 1 3 5\n", "fora.gr"), (2, 5, ParseErrorKind::VertexOutOfRange { vertex: 3, num_vertex: 2, is_zero_based: false }));
+        assert_eq!(erro("p sp 2 1\na  1 2 1O\n", "custo.gr"), (2, 8, ParseErrorKind::InvalidToken { token: "1O".to_string(), expected: "um custo" }));
+        assert_eq!(erro("p sp 2 1\na 1 2\n", "colunas.gr"), (2, 6, ParseErrorKind::WrongColumnCount { expected: 4, found: 3 }));
+        assert_eq!(erro("p sp 2 2\na 1 2 1\n", "contagem.gr"), (1, 8, ParseErrorKind::EdgeCountMismatch { header: 2, found: 1 }));
+        assert_eq!(erro("p sp 2 1\nx 1 2 1\n", "tipo.gr"), (2, 1, ParseErrorKind::InvalidToken { token: "x".to_string(), expected: "uma linha \"c\", \"p\" ou \"a\"" }));
+        assert_eq!(erro("c só comentários\n", "vazio.gr"), (1, 1, ParseErrorKind::EmptyFile));
+    }
+}
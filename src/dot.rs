@@ -0,0 +1,131 @@
+//! # Exportação do grafo para o Graphviz (formato DOT)
+//! ## Conteúdo:
+//! O que destacar no desenho (DotOptions);\
+//! Geração do texto DOT (to_dot()) e escrita em arquivo (write_dot() e write_dot_to()).
+//!
+//! O Graph::print() só mostra a lista de adjacência no terminal. Para as reuniões de revisão o grafo é desenhado com a ferramenta "dot", destacando o resultado dos algoritmos:
+//! ```rust
+//!    let (distancias, anteriores) = dijikstra(&gr, &0);
+//!    let caminho = reconstruir_caminho(0, 6, &anteriores);
+//!    let opcoes = DotOptions { caminho: &caminho, ..Default::default() };
+//!    write_dot(&gr, "caminho.dot", &opcoes)?;
+//!    // no terminal: dot -Tpng caminho.dot -o caminho.png
+//! ```
+
+use std::collections::HashSet;
+use std::io;
+
+pub use crate::error::GraphError;
+pub use crate::graph::{Graph, BufWriter, File, Write};
+pub use crate::weight::Weight;
+
+/// # O que destacar no desenho, todos os vértices são 0-based (como no grafo).
+/// ## Atributos
+/// "caminho" - Um caminho, como o de reconstruir_caminho(), desenhado em vermelho (vazio por padrão);\
+/// "anteriores" - Vetor de anteriores do dijikstra() ou do bellman_ford(), a árvore de caminhos mínimos é desenhada em azul;\
+/// "central" - Vértice marcado como estação central. O def_central_station() retorna o vértice 1-based, então use Some(central_station - 1).
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DotOptions<'a> {
+    pub caminho: &'a [usize],
+    pub anteriores: Option<&'a [Option<usize>]>,
+    pub central: Option<usize>,
+}
+
+/// # Função que gera o texto DOT do grafo.
+/// ## Argumentos
+///
+/// "grafo" - O grafo desenhado;\
+/// "opcoes" - O que destacar.
+///
+/// ## Retorno
+///
+/// O texto DOT. Grafos direcionados viram "digraph" e não-direcionados viram "graph", com cada aresta desenhada uma vez só.\
+/// Os vértices aparecem com o nome (ou o número na indexação original) e as arestas com o peso (ou todos os atributos, se houver mais de um).
+
+pub fn to_dot<W: Weight>(grafo: &Graph<W>, opcoes: &DotOptions) -> String {
+    let mut saida = Vec::new();
+    write_dot_to(grafo, &mut saida, opcoes).expect("escrever em um Vec<u8> não falha");
+    String::from_utf8(saida).expect("o DOT gerado é sempre UTF-8")
+}
+
+/// # Função que salva o DOT do grafo em um arquivo.
+/// ## Retorno
+///
+/// Nada, ou um GraphError::Io com o caminho do arquivo.
+
+pub fn write_dot<W: Weight>(grafo: &Graph<W>, path: &str, opcoes: &DotOptions) -> Result<(), GraphError> {
+    let file = File::create(path).map_err(|e| GraphError::io(path, e))?;
+    let mut writer = BufWriter::new(file);
+    write_dot_to(grafo, &mut writer, opcoes).and_then(|_| writer.flush()).map_err(|e| GraphError::io(path, e))
+}
+
+/// # Função que escreve o DOT do grafo em qualquer destino.
+/// ## Destaques
+/// ```rust
+///    // aresta do caminho: vermelha e grossa; aresta da árvore de anteriores: azul
+///    // vértice do caminho: contorno vermelho; estação central: círculo duplo amarelo
+/// ```
+/// Se uma aresta estiver no caminho e na árvore ao mesmo tempo, vale o destaque do caminho.
+
+pub fn write_dot_to<W: Weight, O: Write>(grafo: &Graph<W>, writer: &mut O, opcoes: &DotOptions) -> io::Result<()> {
+    let ajuste = if grafo.is_zero_based { 0 } else { 1 };
+    let (tipo, seta) = if grafo.is_directed { ("digraph", "->") } else { ("graph", "--") };
+
+    let no_caminho: HashSet<usize> = opcoes.caminho.iter().copied().collect();
+    let arestas_caminho: HashSet<(usize, usize)> = opcoes.caminho.windows(2).map(|par| (par[0], par[1])).collect();
+    let arestas_arvore: HashSet<(usize, usize)> = opcoes
+        .anteriores
+        .unwrap_or(&[])
+        .iter()
+        .enumerate()
+        .filter_map(|(v, anterior)| anterior.map(|a| (a, v)))
+        .collect();
+
+    // Em grafos não-direcionados a aresta conta como destacada nos dois sentidos
+    let destacada = |conjunto: &HashSet<(usize, usize)>, a: usize, b: usize| {
+        conjunto.contains(&(a, b)) || (!grafo.is_directed && conjunto.contains(&(b, a)))
+    };
+
+    writeln!(writer, "{} G {{", tipo)?;
+    writeln!(writer, "    node [shape=circle];")?;
+
+    for v in 0..grafo.num_vertex {
+        let mut atributos = vec![format!("label=\"{}\"", escapar(&grafo.vertex_name(v)))];
+        if opcoes.central == Some(v) {
+            atributos.push("shape=doublecircle, style=filled, fillcolor=gold".to_string());
+        }
+        if no_caminho.contains(&v) {
+            atributos.push("color=red, penwidth=2".to_string());
+        }
+        writeln!(writer, "    {} [{}];", v + ajuste, atributos.join(", "))?;
+    }
+
+    for (origem, node) in grafo.arestas_unicas() {
+        let rotulo = if grafo.attribute_names.len() == 1 {
+            node.weight.to_string()
+        } else {
+            let valores: Vec<String> = grafo.attribute_names
+                .iter()
+                .enumerate()
+                .map(|(i, nome)| format!("{}: {}", nome, node.attribute(i).unwrap_or(W::zero())))
+                .collect();
+            valores.join("\\n")
+        };
+        let mut atributos = vec![format!("label=\"{}\"", rotulo)];
+        if destacada(&arestas_caminho, origem, node.value) {
+            atributos.push("color=red, penwidth=2.5".to_string());
+        } else if destacada(&arestas_arvore, origem, node.value) {
+            atributos.push("color=blue".to_string());
+        }
+        writeln!(writer, "    {} {} {} [{}];", origem + ajuste, seta, node.value + ajuste, atributos.join(", "))?;
+    }
+
+    writeln!(writer, "}}")
+}
+
+/// # Escapa aspas e barras invertidas dos nomes dos vértices
+
+fn escapar(texto: &str) -> String {
+    texto.replace('\\', "\\\\").replace('"', "\\\"")
+}
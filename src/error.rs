@@ -0,0 +1,172 @@
+//! # Erros de leitura dos arquivos de grafo e de mapa
+//! ## Conteúdo:
+//! Erro geral das funções de leitura (GraphError);\
+//! Erro de formatação com arquivo, linha e coluna (ParseError);\
+//! Os tipos de erro de formatação (ParseErrorKind).
+//!
+//! Em vez de ignorar tokens inválidos e devolver um grafo pela metade, as funções de leitura param no primeiro problema e dizem exatamente onde ele está, por exemplo:\
+//! data/graph1.txt:5:9: token inválido "1O" (esperado um peso)
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// # Os tipos de erro de formatação que podem aparecer em um arquivo.
+/// ## Variantes
+/// "EmptyFile" - O arquivo não tem o cabeçalho;\
+/// "InvalidToken" - Um token que não pôde ser convertido (por exemplo "1O" no lugar de "10");\
+/// "WrongColumnCount" - Uma linha com mais ou menos colunas do que o esperado;\
+/// "VertexOutOfRange" - Um vértice fora do intervalo declarado no cabeçalho (como está no arquivo);\
+/// "EdgeCountMismatch" - A quantidade de arestas do cabeçalho é diferente da quantidade de linhas de aresta;\
+/// "RowCountMismatch" - A quantidade de linhas do mapa é diferente da declarada no cabeçalho;\
+/// "DuplicateLabel" - O mesmo nome foi declarado para dois vértices;\
+/// "MisplacedAttributes" - A linha "attrs" está vazia, repetida ou depois das arestas;\
+/// "MissingCell" - O caractere obrigatório do mapa ('S' ou 'G') não foi encontrado;\
+/// "DuplicateEdge" - Aresta repetida (como está no arquivo) com a política ParallelEdges::Reject;\
+/// "Xml" - Arquivo GraphML ou GEXF mal formado, com a mensagem do problema;\
+/// "Json" - Arquivo JSON mal formado ou com campos errados, com a mensagem do problema;\
+/// "MissingColumn" - A coluna pedida não existe no cabeçalho do CSV;\
+/// "TooManyIds" - Com Indexing::Remap, o arquivo tem mais ids diferentes do que os vértices declarados no cabeçalho;\
+/// "InvalidAttributeName" - Nome de atributo que não caberia em uma linha "attrs" (vazio ou com espaços).
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    EmptyFile,
+    InvalidToken { token: String, expected: &'static str },
+    WrongColumnCount { expected: usize, found: usize },
+    VertexOutOfRange { vertex: usize, num_vertex: usize, is_zero_based: bool },
+    EdgeCountMismatch { header: usize, found: usize },
+    RowCountMismatch { header: usize, found: usize },
+    DuplicateLabel(String),
+    MisplacedAttributes,
+    MissingCell(char),
+    DuplicateEdge { origin: usize, destination: usize },
+    Xml(String),
+    Json(String),
+    MissingColumn(String),
+    TooManyIds { id: String, num_vertex: usize },
+    InvalidAttributeName(String),
+}
+
+/// # Erro de formatação, com a posição (1-based) em que ele aconteceu.
+/// ## Atributos
+/// "file" - Caminho do arquivo lido;\
+/// "line" - Linha do problema, começando em 1;\
+/// "column" - Coluna (em caracteres) do início do token com problema, começando em 1;\
+/// "kind" - O que deu errado.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+/// # Erro das funções de leitura.
+/// ## Variantes
+/// "Io" - Falha ao abrir ou ler o arquivo;\
+/// "Parse" - O arquivo foi lido, mas não está na formatação esperada;\
+/// "DuplicateEdge" - A aresta já existe e o grafo recusa arestas paralelas (vértices 0-based, como no grafo);\
+/// "InvalidSnapshot" - O arquivo binário não é um snapshot válido (formato, versão, tipo do peso ou checksum);\
+/// "VertexOutOfRange" - Aresta ou nome para um vértice que não existe no grafo (0-based, como no grafo);\
+/// "SelfLoop" - Laço (aresta de um vértice para ele mesmo) em um GraphBuilder que recusa laços;\
+/// "WrongAttributeCount" - Aresta com uma quantidade de atributos diferente da do grafo;\
+/// "DuplicateLabel" - O mesmo nome foi dado para dois vértices;\
+/// "IncompatibleGraphs" - Os grafos não podem ser unidos (direção, atributos ou nomes diferentes), com a mensagem do problema.
+
+#[derive(Debug)]
+pub enum GraphError {
+    Io { file: String, source: io::Error },
+    Parse(ParseError),
+    DuplicateEdge { origin: usize, destination: usize },
+    InvalidSnapshot { file: String, reason: String },
+    VertexOutOfRange { vertex: usize, num_vertex: usize },
+    SelfLoop { vertex: usize },
+    WrongAttributeCount { expected: usize, found: usize },
+    DuplicateLabel(String),
+    IncompatibleGraphs(String),
+}
+
+impl GraphError {
+
+    /// # Cria um erro de formatação na posição indicada.
+
+    pub fn parse(file: &str, line: usize, column: usize, kind: ParseErrorKind) -> Self {
+        GraphError::Parse(ParseError { file: file.to_string(), line, column, kind })
+    }
+
+    /// # Cria um erro de entrada/saída para o arquivo indicado.
+
+    pub fn io(file: &str, source: io::Error) -> Self {
+        GraphError::Io { file: file.to_string(), source }
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::EmptyFile => write!(f, "arquivo vazio, o cabeçalho não foi encontrado"),
+            ParseErrorKind::InvalidToken { token, expected } => write!(f, "token inválido \"{}\" (esperado {})", token, expected),
+            ParseErrorKind::WrongColumnCount { expected, found } => write!(f, "a linha tem {} colunas, mas eram esperadas {}", found, expected),
+            ParseErrorKind::VertexOutOfRange { vertex, num_vertex, is_zero_based } => {
+                if *is_zero_based {
+                    write!(f, "vértice {} fora do intervalo 0..={}", vertex, num_vertex.saturating_sub(1))
+                } else {
+                    write!(f, "vértice {} fora do intervalo 1..={}", vertex, num_vertex)
+                }
+            }
+            ParseErrorKind::EdgeCountMismatch { header, found } => write!(f, "o cabeçalho declara {} arestas, mas o arquivo tem {}", header, found),
+            ParseErrorKind::RowCountMismatch { header, found } => write!(f, "o cabeçalho declara {} linhas, mas o mapa tem {}", header, found),
+            ParseErrorKind::DuplicateLabel(nome) => write!(f, "o nome \"{}\" já foi usado em outro vértice", nome),
+            ParseErrorKind::MisplacedAttributes => write!(f, "a declaração de atributos precisa ter ao menos um nome e vir uma única vez, antes das arestas"),
+            ParseErrorKind::MissingCell(ch) => write!(f, "o caractere '{}' não foi encontrado no mapa", ch),
+            ParseErrorKind::DuplicateEdge { origin, destination } => write!(f, "a aresta ({}, {}) já existe e arestas paralelas são recusadas", origin, destination),
+            ParseErrorKind::Xml(mensagem) => write!(f, "XML inválido: {}", mensagem),
+            ParseErrorKind::Json(mensagem) => write!(f, "JSON inválido: {}", mensagem),
+            ParseErrorKind::MissingColumn(nome) => write!(f, "a coluna \"{}\" não existe no cabeçalho", nome),
+            ParseErrorKind::TooManyIds { id, num_vertex } => write!(f, "o id \"{}\" passa dos {} vértices declarados no cabeçalho", id, num_vertex),
+            ParseErrorKind::InvalidAttributeName(nome) => write!(f, "o nome de atributo {:?} não pode ter espaços nem ser vazio", nome),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}: {}", self.file, self.line, self.column, self.kind)
+    }
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::Io { file, source } => write!(f, "{}: {}", file, source),
+            GraphError::Parse(erro) => write!(f, "{}", erro),
+            GraphError::DuplicateEdge { origin, destination } => write!(f, "a aresta ({}, {}) já existe e arestas paralelas são recusadas", origin, destination),
+            GraphError::InvalidSnapshot { file, reason } => write!(f, "{}: snapshot inválido: {}", file, reason),
+            GraphError::VertexOutOfRange { vertex, num_vertex } => write!(f, "vértice {} fora do intervalo 0..{}", vertex, num_vertex),
+            GraphError::SelfLoop { vertex } => write!(f, "laço no vértice {} e o grafo recusa laços", vertex),
+            GraphError::WrongAttributeCount { expected, found } => write!(f, "a aresta tem {} atributos, mas eram esperados {}", found, expected),
+            GraphError::DuplicateLabel(nome) => write!(f, "o nome \"{}\" já foi usado em outro vértice", nome),
+            GraphError::IncompatibleGraphs(mensagem) => write!(f, "os grafos não são compatíveis: {}", mensagem),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Error for GraphError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GraphError::Io { source, .. } => Some(source),
+            GraphError::Parse(erro) => Some(erro),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for GraphError {
+    fn from(erro: ParseError) -> Self {
+        GraphError::Parse(erro)
+    }
+}
@@ -4,9 +4,11 @@
 //! Associação com o livro (floyd_rot_n_cost())
 
 pub use crate::graph::Graph;
+pub use crate::graph_trait::WeightedGraph;
+pub use crate::weight::Weight;
 
 /// # Função de backtracking para reconstrução do caminho
-pub fn reconstruir_caminho(rot: &Vec<Vec<Option<usize>>>, origem: usize, destino: usize) -> Vec<usize> {
+pub fn reconstruir_caminho(rot: &[Vec<Option<usize>>], origem: usize, destino: usize) -> Vec<usize> {
     let mut caminho = vec![origem];
     let mut head = origem;
 
@@ -25,48 +27,51 @@ pub fn reconstruir_caminho(rot: &Vec<Vec<Option<usize>>>, origem: usize, destino
 /// # Algoritmo e associação com o livro.
 /// ## Argumentos
 /// 
-/// "gr" - O grafo analisado.
+/// "gr" - O grafo analisado (qualquer representação que implemente WeightedGraph).
 ///
 /// ## Retorno
 /// 
 /// Matriz de custo de todos os vértices para todos os vértices;\
 /// Matriz de rotemento, de todos os vértices oara todos os vértices, para a reconstruçao do caminho.
 /// 
+/// ## Pares sem caminho
+/// Continuam com INFINITO e rota None, mesmo com arestas negativas no grafo.
+///
+/// ## Desempate
+/// Entre arestas paralelas vale a de menor peso (a primeira incluída, se os pesos forem iguais), e um laço só entra na matriz se for negativo.\
+/// Entre dois caminhos de mesmo custo fica o que foi encontrado primeiro: a troca só acontece com Dik + Dkj estritamente menor, então vence o caminho que usa apenas intermediários de índice menor.
+/// 
 /// ## Associação com livro
 /// ```rust
-/// let inf = i32::MAX/2;
+/// let inf = W::infinity();
 ///    let num_v = gr.num_vertex();
 ///    let mut rot = vec![vec![None; num_v]; num_v]; // Matriz de roteamento Rij
 ///    let mut cost = vec![vec![inf; num_v]; num_v]; // matriz de custo D^0
 ///
 ///    for i in 0..num_v{
 ///        rot[i][i] = Some(i); // Caminho dele para ele mesmo é ele
-///        cost[i][i] = 0; // Custo do vétice para ele mesmo é 0
+///        cost[i][i] = W::zero(); // Custo do vétice para ele mesmo é 0
 ///    }
 /// 
-///    for (origin, adj_list) in &gr.adj // Pega o par de origem e os nós ligados a origem
+///    for origin in 0..num_v // Pega cada origem
 ///    {
-///        if let Some(mut node) = adj_list.as_deref() // Pega a lista de nós
+///        for (destination, weight) in gr.neighbors_with_weights(origin) // Pega os vizinhos ligados a origem
 ///        {
-///            loop{
-///                rot[*origin][node.value] = Some(node.value); // Rij <- j
-///                cost[*origin][node.value] = node.weight; // Dij <- V(G)
-///
-///                if let Some(next_node) = node.next.as_deref(){
-///                    node = next_node; // Chama próximo nó
-///                }
-///                
-///                else{
-///                    break; // Não existe mais nós
-///                }
+///            if weight < cost[origin][destination] // Arestas paralelas: fica a de menor peso
+///            {
+///                rot[origin][destination] = Some(destination); // Rij <- j
+///                cost[origin][destination] = weight; // Dij <- V(G)
 ///            }
 ///        }
 ///    }
 ///
 ///    for k in 0..num_v{ // Para k = 0 ... n
 ///        for i in 0..num_v{ // Para i = 0 ... n
+///            if cost[i][k] == inf { // Sem caminho de i até k: nos inteiros INFINITO + Dkj negativo daria um custo finito
+///                continue;
+///            }
 ///            for j in 0..num_v{ // Para j = 0 ... n
-///                if (cost[i][k] + cost[k][j]) < cost[i][j] // if Dik + Dkj < Dij
+///                if cost[k][j] != inf && (cost[i][k] + cost[k][j]) < cost[i][j] // if Dik + Dkj < Dij
 ///                {
 ///                    cost[i][j] = cost[i][k] + cost[k][j]; // Dij <- Dik + Dkj
 ///                    rot[i][j] = rot[i][k]; // Rij <- Rik
@@ -77,41 +82,37 @@ pub fn reconstruir_caminho(rot: &Vec<Vec<Option<usize>>>, origem: usize, destino
 ///
 ///    (cost, rot) // Retorno
 /// ```
-pub fn floyd_rot_n_cost(gr : &Graph) -> (Vec<Vec<i32>>, Vec<Vec<Option<usize>>>){ //retorna as duas matrizes
+pub fn floyd_rot_n_cost<W: Weight, G: WeightedGraph<W>>(gr : &G) -> (Vec<Vec<W>>, Vec<Vec<Option<usize>>>){ //retorna as duas matrizes
 
-    let inf = i32::MAX/2;
+    let inf = W::infinity();
     let num_v = gr.num_vertex();
     let mut rot = vec![vec![None; num_v]; num_v]; // Matriz de roteamento Rij
     let mut cost = vec![vec![inf; num_v]; num_v]; // matriz de custo D^0
 
     for i in 0..num_v{
         rot[i][i] = Some(i); // Caminho dele para ele mesmo é ele
-        cost[i][i] = 0; // Custo do vétice para ele mesmo é 0
+        cost[i][i] = W::zero(); // Custo do vétice para ele mesmo é 0
     }
 
-    for (origin, adj_list) in &gr.adj // Pega o par de origem e os nós ligados a origem
+    for origin in 0..num_v // Pega cada origem
     {
-        if let Some(mut node) = adj_list.as_deref() // Pega a lista de nós
+        for (destination, weight) in gr.neighbors_with_weights(origin) // Pega os vizinhos ligados a origem
         {
-            loop{
-                rot[*origin][node.value] = Some(node.value); // Rij <- j
-                cost[*origin][node.value] = node.weight; // Dij <- V(G)
-
-                if let Some(next_node) = node.next.as_deref(){
-                    node = next_node; // Chama próximo nó
-                }
-                
-                else{
-                    break; // Não existe mais nós
-                }
+            if weight < cost[origin][destination] // Arestas paralelas: fica a de menor peso
+            {
+                rot[origin][destination] = Some(destination); // Rij <- j
+                cost[origin][destination] = weight; // Dij <- V(G)
             }
         }
     }
 
     for k in 0..num_v{ // Para k = 0 ... n
         for i in 0..num_v{ // Para i = 0 ... n
+            if cost[i][k] == inf { // Sem caminho de i até k: nos inteiros INFINITO + Dkj negativo daria um custo finito
+                continue;
+            }
             for j in 0..num_v{ // Para j = 0 ... n
-                if (cost[i][k] + cost[k][j]) < cost[i][j] // if Dik + Dkj < Dij
+                if cost[k][j] != inf && (cost[i][k] + cost[k][j]) < cost[i][j] // if Dik + Dkj < Dij
                 {
                     cost[i][j] = cost[i][k] + cost[k][j]; // Dij <- Dik + Dkj
                     rot[i][j] = rot[i][k]; // Rij <- Rik
@@ -121,4 +122,33 @@ pub fn floyd_rot_n_cost(gr : &Graph) -> (Vec<Vec<i32>>, Vec<Vec<Option<usize>>>)
     }
 
     (cost, rot) // Retorno
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn par_sem_caminho_com_aresta_negativa_continua_infinito() {
+        // "3 1\n1 2 -5": a aresta negativa sai do vértice 1, que não é alcançável a partir do 0
+        let mut gr: Graph = Graph::new(true, 3);
+        gr.edge(1, 2, -5).unwrap();
+        let (custo, rot) = floyd_rot_n_cost(&gr);
+        let inf = i32::infinity();
+        assert_eq!(custo, vec![vec![0, inf, inf], vec![inf, 0, -5], vec![inf, inf, 0]]);
+        assert_eq!(rot[0][2], None);
+        assert_eq!(reconstruir_caminho(&rot, 0, 2), Vec::<usize>::new());
+        assert_eq!(reconstruir_caminho(&rot, 1, 2), vec![1, 2]);
+    }
+
+    #[test]
+    fn caminho_com_aresta_negativa_passa_pelo_intermediario() {
+        let mut gr: Graph = Graph::new(true, 3);
+        gr.edge(0, 1, 4).unwrap();
+        gr.edge(1, 2, -5).unwrap();
+        gr.edge(0, 2, 1).unwrap();
+        let (custo, rot) = floyd_rot_n_cost(&gr);
+        assert_eq!(custo[0][2], -1);
+        assert_eq!(reconstruir_caminho(&rot, 0, 2), vec![0, 1, 2]);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,424 @@
+//! # Geradores de grafos aleatórios com semente
+//! ## Conteúdo:
+//! Gerador de números pseudo-aleatórios (SplitMix64);\
+//! Opções comuns dos geradores (GeneratorOptions);\
+//! Modelos de grafo: Erdős–Rényi (erdos_renyi()), geométrico aleatório (random_geometric()), Barabási–Albert (barabasi_albert()) e DAG com pesos negativos (random_dag());\
+//! Mapa do "Cenário 3" com obstáculos (random_grid()) e o grafo do grid (grid_graph()).
+//!
+//! Com a mesma semente o grafo gerado é sempre o mesmo, em qualquer máquina, então dá para estressar os algoritmos com entradas de qualquer tamanho e repetir exatamente o mesmo teste:
+//! ```rust
+//!    let opcoes = GeneratorOptions { seed: 42, ..Default::default() };
+//!    let gr: Graph = erdos_renyi(10_000, 0.001, &opcoes);
+//!    let (distancias, anteriores) = dijikstra(&gr, &0);
+//!
+//!    let dag: Graph = random_dag(500, 0.05, &GeneratorOptions { seed: 7, weights: -50..=50, ..Default::default() });
+//!    let (distancias, anteriores) = bellman_ford(&dag, &0); // sem ciclos, então sem ciclo negativo
+//! ```
+//! No DAG a origem cai em uma posição aleatória da ordem topológica, então boa parte dos vértices não é alcançável a partir dela: esses ficam com W::infinity() e sem anterior.
+//! Os pesos são sorteados como i32 e convertidos para o tipo do peso, então os geradores servem para Graph<i32>, Graph<i64>, Graph<f64>...
+
+use std::ops::RangeInclusive;
+
+pub use crate::graph::{Graph, Mapa, get_peso};
+pub use crate::weight::Weight;
+
+/// # Gerador pseudo-aleatório SplitMix64: pequeno, rápido e com a mesma sequência em qualquer plataforma.
+/// Não serve para criptografia, só para gerar entradas reproduzíveis.
+
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    estado: u64,
+}
+
+impl SplitMix64 {
+
+    /// # Cria o gerador a partir da semente
+
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { estado: seed }
+    }
+
+    /// # Próximo número de 64 bits da sequência
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.estado = self.estado.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.estado;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// # Número real no intervalo [0, 1)
+
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// # Inteiro no intervalo 0..n (n precisa ser maior que 0)
+
+    pub fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// # Inteiro no intervalo fechado, por exemplo -50..=50
+
+    pub fn between(&mut self, intervalo: &RangeInclusive<i32>) -> i32 {
+        let tamanho = (*intervalo.end() as i64 - *intervalo.start() as i64 + 1) as usize;
+        (*intervalo.start() as i64 + self.below(tamanho) as i64) as i32
+    }
+
+    /// # true com probabilidade p
+
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    /// # Embaralha o vetor (Fisher–Yates)
+
+    pub fn shuffle<T>(&mut self, vetor: &mut [T]) {
+        for i in (1..vetor.len()).rev() {
+            vetor.swap(i, self.below(i + 1));
+        }
+    }
+
+    /// # Quantos sorteios de probabilidade p falham antes do próximo sucesso, para pular direto para a próxima aresta em grafos esparsos
+    /// None quando o pulo não cabe em um usize (com p muito pequeno), ou seja, não há próximo sucesso.
+
+    fn pulo(&mut self, p: f64) -> Option<usize> {
+        if p >= 1.0 {
+            return Some(0);
+        }
+        // ln_1p() no lugar de (1.0 - p).ln(), que arredonda para ln(1) = 0 quando p é menor que ~1e-16
+        let pulo = ((-self.next_f64()).ln_1p() / (-p).ln_1p()).floor();
+        if pulo.is_finite() && pulo < usize::MAX as f64 { Some(pulo as usize) } else { None }
+    }
+
+    /// # Índice do próximo par sorteado depois de "indice", ou None se não houver mais nenhum
+
+    fn proximo_par(&mut self, indice: usize, p: f64) -> Option<usize> {
+        self.pulo(p).and_then(|pulo| indice.checked_add(1)?.checked_add(pulo))
+    }
+}
+
+/// # Opções comuns dos geradores.
+/// ## Atributos
+/// "seed" - Semente do SplitMix64 (0 por padrão);\
+/// "weights" - Intervalo dos pesos sorteados (1..=100 por padrão). Pode ter pesos negativos, mas só o random_dag() garante que não há ciclo negativo;\
+/// "is_directed" - Se o grafo gerado tem direção (true por padrão). Nos modelos simétricos (geométrico e Barabási–Albert) cada ligação vira as duas arestas, ida e volta.
+
+#[derive(Debug, Clone)]
+pub struct GeneratorOptions {
+    pub seed: u64,
+    pub weights: RangeInclusive<i32>,
+    pub is_directed: bool,
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        GeneratorOptions { seed: 0, weights: 1..=100, is_directed: true }
+    }
+}
+
+/// # Grafo vazio (0-based) com a direção das opções
+fn grafo_vazio<W: Weight>(num_vertex: usize, opcoes: &GeneratorOptions) -> Graph<W> {
+    if opcoes.is_directed { Graph::new(true, num_vertex) } else { Graph::new_undirected(true, num_vertex) }
+}
+
+/// # Inclui a aresta sorteando o peso
+fn incluir<W: Weight + From<i32>>(grafo: &mut Graph<W>, rng: &mut SplitMix64, opcoes: &GeneratorOptions, origem: usize, destino: usize) {
+    let peso = W::from(rng.between(&opcoes.weights));
+    grafo.incluir_aresta(origem, destino, &[peso]);
+}
+
+/// # Inclui a ligação nos dois sentidos, como o add_undirected_edge() (em grafos não-direcionados uma aresta só)
+fn ligar<W: Weight>(grafo: &mut Graph<W>, u: usize, v: usize, peso: W) {
+    grafo.incluir_aresta(u, v, &[peso]);
+    if grafo.is_directed && u != v {
+        grafo.incluir_aresta(v, u, &[peso]);
+    }
+}
+
+/// # Modelo de Erdős–Rényi G(n, p): cada aresta possível existe com probabilidade p, independente das outras.
+/// ## Argumentos
+/// "n" - Quantidade de vértices;\
+/// "p" - Probabilidade de cada aresta (sem laços). Em grafos direcionados (u, v) e (v, u) são sorteadas separadamente;\
+/// "opcoes" - Semente, pesos e direção.
+///
+/// ## Retorno
+/// O grafo, com cerca de p * n * (n - 1) arestas (metade disso se não for direcionado).
+/// ## Desempenho
+/// Em vez de sortear cada par, o gerador pula direto para o próximo par sorteado, então o tempo é proporcional à quantidade de arestas e não a n².
+
+pub fn erdos_renyi<W: Weight + From<i32>>(n: usize, p: f64, opcoes: &GeneratorOptions) -> Graph<W> {
+    let mut rng = SplitMix64::new(opcoes.seed);
+    let mut grafo = grafo_vazio(n, opcoes);
+    if n < 2 || p <= 0.0 {
+        return grafo;
+    }
+
+    // Os pares possíveis são numerados: (u, v) com v != u no direcionado, (u, v) com v < u no não-direcionado
+    let total = if opcoes.is_directed { n * (n - 1) } else { n * (n - 1) / 2 };
+    let mut proximo = rng.pulo(p);
+    // No não-direcionado a linha u tem u pares, começando no índice u * (u - 1) / 2
+    let (mut u, mut inicio_linha) = (1, 0);
+    while let Some(indice) = proximo
+        && indice < total
+    {
+        let (origem, destino) = if opcoes.is_directed {
+            let (origem, k) = (indice / (n - 1), indice % (n - 1));
+            (origem, if k >= origem { k + 1 } else { k })
+        } else {
+            while indice >= inicio_linha + u {
+                inicio_linha += u;
+                u += 1;
+            }
+            (u, indice - inicio_linha)
+        };
+        incluir(&mut grafo, &mut rng, opcoes, origem, destino);
+        proximo = rng.proximo_par(indice, p);
+    }
+
+    grafo
+}
+
+/// # Grafo geométrico aleatório: n pontos sorteados no quadrado [0, 1) x [0, 1), ligados quando a distância é no máximo "raio".
+/// ## Pesos
+/// O peso cresce com a distância: pontos colados recebem o início de "weights" e pontos a exatamente "raio" recebem o fim, como uma rede de ruas em que a ligação mais longa é a mais cara.
+/// ## Desempenho
+/// Os pontos são separados em células de lado "raio" (ou maiores, para que não existam mais de ~n células com raios muito pequenos), e cada ponto só é comparado com os das células vizinhas.
+
+pub fn random_geometric<W: Weight + From<i32>>(n: usize, raio: f64, opcoes: &GeneratorOptions) -> Graph<W> {
+    let mut rng = SplitMix64::new(opcoes.seed);
+    let mut grafo = grafo_vazio(n, opcoes);
+    let pontos: Vec<(f64, f64)> = (0..n).map(|_| (rng.next_f64(), rng.next_f64())).collect();
+    if raio <= 0.0 {
+        return grafo;
+    }
+
+    let lado = ((1.0 / raio).floor() as usize).clamp(1, ((n as f64).sqrt().ceil() as usize).max(1));
+    let celula = |(x, y): (f64, f64)| (((x * lado as f64) as usize).min(lado - 1), ((y * lado as f64) as usize).min(lado - 1));
+    let mut celulas: Vec<Vec<usize>> = vec![Vec::new(); lado * lado];
+    for (v, &ponto) in pontos.iter().enumerate() {
+        let (cx, cy) = celula(ponto);
+        celulas[cy * lado + cx].push(v);
+    }
+
+    let (inicio, fim) = (*opcoes.weights.start() as f64, *opcoes.weights.end() as f64);
+    for (u, &(ux, uy)) in pontos.iter().enumerate() {
+        let (cx, cy) = celula((ux, uy));
+        let mut vizinhos: Vec<usize> = Vec::new();
+        for vy in cy.saturating_sub(1)..=(cy + 1).min(lado - 1) {
+            for vx in cx.saturating_sub(1)..=(cx + 1).min(lado - 1) {
+                vizinhos.extend(celulas[vy * lado + vx].iter().copied().filter(|&v| v > u));
+            }
+        }
+        vizinhos.sort_unstable();
+        for v in vizinhos {
+            let (vx, vy) = pontos[v];
+            let distancia = ((ux - vx).powi(2) + (uy - vy).powi(2)).sqrt();
+            if distancia <= raio {
+                let peso = (inicio + (distancia / raio) * (fim - inicio)).round() as i32;
+                ligar(&mut grafo, u, v, W::from(peso));
+            }
+        }
+    }
+
+    grafo
+}
+
+/// # Modelo de Barabási–Albert: cada vértice novo se liga a m vértices já existentes, escolhidos com probabilidade proporcional ao grau.
+/// Gera redes com poucos vértices muito conectados (hubs), como redes de transporte e de comunicação.
+/// ## Argumentos
+/// "n" - Quantidade de vértices;\
+/// "m" - Ligações de cada vértice novo (pelo menos 1). Os m primeiros vértices começam ligados em caminho;\
+/// "opcoes" - Semente, pesos e direção.
+///
+/// ## Retorno
+/// O grafo conexo com (m - 1) + (n - m) * m ligações, sem arestas paralelas.
+
+pub fn barabasi_albert<W: Weight + From<i32>>(n: usize, m: usize, opcoes: &GeneratorOptions) -> Graph<W> {
+    assert!(m >= 1, "Each new vertex needs at least one link.");
+    let mut rng = SplitMix64::new(opcoes.seed);
+    let mut grafo = grafo_vazio(n, opcoes);
+    let m = m.min(n);
+
+    // Cada vértice aparece uma vez para cada ligação, então sortear uma posição é sortear proporcional ao grau
+    let mut pontas: Vec<usize> = Vec::new();
+    let ligar_ponta = |grafo: &mut Graph<W>, rng: &mut SplitMix64, u: usize, v: usize, pontas: &mut Vec<usize>| {
+        ligar(grafo, u, v, W::from(rng.between(&opcoes.weights)));
+        pontas.extend([u, v]);
+    };
+
+    for v in 1..m {
+        ligar_ponta(&mut grafo, &mut rng, v - 1, v, &mut pontas);
+    }
+    for novo in m..n {
+        let mut escolhidos: Vec<usize> = Vec::with_capacity(m);
+        while escolhidos.len() < m {
+            let alvo = if pontas.is_empty() { rng.below(novo) } else { pontas[rng.below(pontas.len())] };
+            if !escolhidos.contains(&alvo) {
+                escolhidos.push(alvo);
+            }
+        }
+        for alvo in escolhidos {
+            ligar_ponta(&mut grafo, &mut rng, novo, alvo, &mut pontas);
+        }
+    }
+
+    grafo
+}
+
+/// # Grafo acíclico direcionado (DAG) com os pesos de "weights", que podem ser negativos.
+/// Os vértices são embaralhados em uma ordem topológica aleatória e cada par (anterior, posterior) nessa ordem vira uma aresta com probabilidade p. Como não há ciclos, não há ciclo negativo, e o bellman_ford() sempre termina.\
+/// Só os vértices depois da origem na ordem topológica podem ser alcançados a partir dela.\
+/// O grafo é sempre direcionado ("is_directed" das opções é ignorado).
+
+pub fn random_dag<W: Weight + From<i32>>(n: usize, p: f64, opcoes: &GeneratorOptions) -> Graph<W> {
+    let mut rng = SplitMix64::new(opcoes.seed);
+    let mut grafo = Graph::new(true, n);
+    let mut ordem: Vec<usize> = (0..n).collect();
+    rng.shuffle(&mut ordem);
+    if n < 2 || p <= 0.0 {
+        return grafo;
+    }
+
+    // Mesma numeração dos pares do erdos_renyi() não-direcionado: (i, j) com j < i nas posições da ordem
+    let total = n * (n - 1) / 2;
+    let mut proximo = rng.pulo(p);
+    let (mut i, mut inicio_linha) = (1, 0);
+    while let Some(indice) = proximo
+        && indice < total
+    {
+        while indice >= inicio_linha + i {
+            inicio_linha += i;
+            i += 1;
+        }
+        let j = indice - inicio_linha;
+        incluir(&mut grafo, &mut rng, opcoes, ordem[j], ordem[i]);
+        proximo = rng.proximo_par(indice, p);
+    }
+
+    grafo
+}
+
+/// # Mapa aleatório do "Cenário 3", no mesmo formato do read_map().
+/// ## Argumentos
+/// "linhas" e "colunas" - Tamanho do grid;\
+/// "obstaculos" - Fração das células que viram estantes '#' (de 0.0 a 1.0);\
+/// "piso_dificil" - Fração das células livres que viram piso difícil '~';\
+/// "seed" - Semente do SplitMix64.
+///
+/// ## Retorno
+/// O mapa com 'S' no canto superior esquerdo e 'G' no canto inferior direito. Com muitos obstáculos pode não existir caminho de 'S' até 'G'.
+
+pub fn random_grid(linhas: usize, colunas: usize, obstaculos: f64, piso_dificil: f64, seed: u64) -> Mapa {
+    assert!(linhas > 0 && colunas > 0, "The grid needs at least one cell.");
+    let mut rng = SplitMix64::new(seed);
+    let mut matrix: Vec<Vec<char>> = (0..linhas)
+        .map(|_| {
+            (0..colunas)
+                .map(|_| if rng.chance(obstaculos) { '#' } else if rng.chance(piso_dificil) { '~' } else { '.' })
+                .collect()
+        })
+        .collect();
+
+    let (start, goal) = ((0, 0), (colunas - 1, linhas - 1));
+    matrix[start.1][start.0] = 'S';
+    matrix[goal.1][goal.0] = 'G';
+    (matrix, start, goal)
+}
+
+/// # Grafo do grid, igual ao que o map_to_txt() salva e o "Cenário 3" lê, mas sem passar por arquivo.
+/// Cada célula é o vértice x + y * colunas (achar_node() do "Cenário 3") e tem uma aresta para cada vizinho nas 4 direções que não é obstáculo, com o peso do terreno do vizinho (get_peso()).\
+/// Os vértices são mostrados 1-based, como no "graph3.txt".
+
+pub fn grid_graph<W: Weight + From<i32>>(matrix: &[Vec<char>]) -> Graph<W> {
+    let linhas = matrix.len();
+    let colunas = matrix.first().map_or(0, |linha| linha.len());
+    let mut grafo = Graph::new(false, linhas * colunas);
+
+    for y in 0..linhas {
+        for x in 0..colunas {
+            let vizinhos = [(y.wrapping_sub(1), x), (y + 1, x), (y, x.wrapping_sub(1)), (y, x + 1)];
+            for (viz_y, viz_x) in vizinhos {
+                if viz_y < linhas && viz_x < colunas
+                    && let Some(peso) = get_peso(matrix[viz_y][viz_x])
+                {
+                    grafo.incluir_aresta(x + y * colunas, viz_x + viz_y * colunas, &[W::from(peso)]);
+                }
+            }
+        }
+    }
+
+    grafo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bellman_ford::bellman_ford;
+
+    /// # Marca os vértices alcançáveis a partir da origem (busca em largura)
+
+    fn alcancaveis(grafo: &Graph, origem: usize) -> Vec<bool> {
+        let mut visitado = vec![false; grafo.num_vertex];
+        let mut fila = std::collections::VecDeque::from([origem]);
+        visitado[origem] = true;
+        while let Some(v) = fila.pop_front() {
+            for (vizinho, _) in grafo.neighbors_with_weights(v) {
+                if !visitado[vizinho] {
+                    visitado[vizinho] = true;
+                    fila.push_back(vizinho);
+                }
+            }
+        }
+        visitado
+    }
+
+    #[test]
+    fn dag_com_pesos_negativos_deixa_os_inalcancaveis_no_infinito() {
+        let dag: Graph = random_dag(100, 0.1, &GeneratorOptions { seed: 7, weights: -50..=50, ..Default::default() });
+        let (distancias, anteriores) = bellman_ford(&dag, &0);
+        let alcancado = alcancaveis(&dag, 0);
+        assert!(alcancado.iter().any(|&a| !a), "a semente precisa deixar vértices fora do alcance da origem");
+
+        for v in 0..dag.num_vertex {
+            if alcancado[v] {
+                assert!(distancias[v] < i32::infinity() / 2, "vértice {} alcançável com custo {}", v, distancias[v]);
+                assert_eq!(anteriores[v].is_none(), v == 0);
+            } else {
+                assert_eq!(distancias[v], i32::infinity(), "vértice {} inalcançável", v);
+                assert_eq!(anteriores[v], None);
+            }
+        }
+    }
+
+    type Gerador = fn(&GeneratorOptions) -> Graph;
+
+    /// # Arestas do grafo gerado com a semente, para comparar duas execuções
+
+    fn arestas_com_semente(gerar: Gerador, seed: u64) -> Vec<(usize, usize, i32)> {
+        gerar(&GeneratorOptions { seed, ..Default::default() }).edges().collect()
+    }
+
+    #[test]
+    fn mesma_semente_gera_o_mesmo_grafo() {
+        let geradores: [(&str, Gerador); 4] = [
+            ("erdos_renyi", |opcoes| erdos_renyi(200, 0.05, opcoes)),
+            ("random_geometric", |opcoes| random_geometric(200, 0.1, opcoes)),
+            ("barabasi_albert", |opcoes| barabasi_albert(200, 3, opcoes)),
+            ("random_dag", |opcoes| random_dag(200, 0.05, opcoes)),
+        ];
+
+        for (nome, gerar) in geradores {
+            let primeira = arestas_com_semente(gerar, 42);
+            assert!(!primeira.is_empty(), "{} não gerou arestas", nome);
+            assert_eq!(primeira, arestas_com_semente(gerar, 42), "{} mudou com a mesma semente", nome);
+            assert_ne!(primeira, arestas_com_semente(gerar, 43), "{} ignorou a semente", nome);
+            assert!(primeira.iter().all(|(_, _, peso)| (1..=100).contains(peso)), "{} gerou peso fora do intervalo", nome);
+        }
+
+        assert_eq!(random_grid(30, 40, 0.2, 0.3, 42), random_grid(30, 40, 0.2, 0.3, 42));
+        assert_ne!(random_grid(30, 40, 0.2, 0.3, 42), random_grid(30, 40, 0.2, 0.3, 43));
+    }
+}
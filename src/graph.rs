@@ -1,11 +1,16 @@
-//! # Concentra a lógica da construção do grafo, o método escolhido foi representação com lista de adjacência, para uma melhor eficiência de memória e para criar familiaridade com a estrutra de HashMap do Rust.
-//! Importante destacar que nosso grafo é 0-based, já que se a quantidade de vértice for 12 nossa Hash terá indices de 0 a 11.\
+//! # Concentra a lógica da construção do grafo, o método escolhido foi representação com lista de adjacência, para uma melhor eficiência de memória e para criar familiaridade com as coleções de mapas do Rust.
+//! Importante destacar que nosso grafo é 0-based, já que se a quantidade de vértice for 12 nosso mapa terá indices de 0 a 11.\
 //! Por exemplo, o grafo de exemplo "graph1.txt" tem 12 vértices, enumerados de 1 a 12, quando lemos, transformamos eles em 12 vértices, enumerados de 0 a 11.
 //! Já em "graoh2.txt" temos 7 vértices, enumerados de 0 a 6, por isso não precisamos converter.
+//! ## Ordem determinística
+//! As listas de adjacência ficam em um BTreeMap (e não em um HashMap), então toda a API percorre o grafo sempre na mesma ordem, de uma execução para outra:\
+//! Os vértices em ordem crescente (print(), edges(), in_neighbors(), as escritas em arquivo, CsrGraph e snapshot);\
+//! Os vizinhos de cada vértice na ordem em que as arestas foram incluídas (ou seja, a ordem das linhas do arquivo lido).\
+//! Os algoritmos dependem só dessa ordem, e o desempate entre caminhos de mesmo custo está documentado em cada um deles.
 
-/// # Responsável a coleção de HashMap para a representação do grafo.
+/// # Responsável a coleção de BTreeMap para a representação do grafo (e do HashMap, usado nas tabelas auxiliares da leitura).
 
-pub use std::collections::HashMap;
+pub use std::collections::{BTreeMap, HashMap};
 /// # Responável pela possibilidade de leituras de arquivo txt no código em todos cenários.
 
 pub use std::fs::File;
@@ -17,40 +22,66 @@ pub use std::fs;
 pub use std::io;
 pub use std::error::Error;
 
+pub use crate::error::{GraphError, ParseErrorKind};
+pub use crate::labels::Labels;
+pub use crate::weight::Weight;
+
 /// # Representa o nó do grafo.
 /// ## Atributos
 /// "value" - Valor do vértice (sempre representado por um inteiro positivo);\
-/// "weight" - Valor do peso da aresta (podendo ser negativo ou positivo);\
+/// "weight" - Valor do peso da aresta (podendo ser negativo ou positivo, de qualquer tipo que implemente Weight), é o primeiro atributo da aresta;\
+/// "extra" - Demais atributos da aresta (tempo, energia...), na ordem de Graph::attribute_names a partir do segundo. Vazio quando a aresta só tem peso;\
 /// "next" - Inclui uma referência para o próximo nó.
 
 #[derive(Debug)]
-pub struct Node {
+pub struct Node<W = i32> {
     pub value: usize,
-    pub weight: i32,
-    pub next: Option<Box<Node>>,
+    pub weight: W,
+    pub extra: Vec<W>,
+    pub next: Option<Box<Node<W>>>,
 }
 
-impl Node {
+impl<W: Weight> Node<W> {
 
     /// # Função para crianção de um novo nó.
     
-    pub fn new(value: usize, weight: i32) -> Self {
-        Node {value, weight, next: None}
+    pub fn new(value: usize, weight: W) -> Self {
+        Node {value, weight, extra: Vec::new(), next: None}
+    }
+
+    /// # Função para crianção de um novo nó com vários atributos (o primeiro é o peso).
+
+    pub fn with_attributes(value: usize, atributos: &[W]) -> Self {
+        Node {value, weight: atributos[0], extra: atributos[1..].to_vec(), next: None}
+    }
+
+    /// # Retorna o atributo de índice i da aresta (0 é o peso), se existir
+
+    pub fn attribute(&self, i: usize) -> Option<W> {
+        if i == 0 { Some(self.weight) } else { self.extra.get(i - 1).copied() }
     }
 
     /// # Função para incluir um nó na lista.
 
-    pub fn append(&mut self, value: usize, weight: i32) {
-        match &mut self.next {
-            Some(next_node) => next_node.append(value, weight),
-            None => self.next = Some(Box::new(Node::new(value, weight))),
+    pub fn append(&mut self, value: usize, weight: W) {
+        self.append_node(Node::new(value, weight));
+    }
+
+    /// # Função para incluir um nó já criado no fim da lista.
+    /// Percorre a lista de forma iterativa, para não estourar a pilha em vértices com muitos vizinhos.
+
+    pub fn append_node(&mut self, novo: Node<W>) {
+        let mut atual = &mut self.next;
+        while let Some(next_node) = atual {
+            atual = &mut next_node.next;
         }
+        *atual = Some(Box::new(novo));
     }
 
     /// # Função para printar o nó;
 
     pub fn print(&self, is_zero_indexed: bool) {
-        if is_zero_indexed == false{
+        if !is_zero_indexed {
             print!("{} (weight: {})", self.value + 1, self.weight);
         }
         else{
@@ -63,22 +94,98 @@ impl Node {
     }
 }
 
+/// # Libera a lista de forma iterativa: o Drop padrão do Box é recursivo e estoura a pilha em vértices com muitos vizinhos.
+
+impl<W> Drop for Node<W> {
+    fn drop(&mut self) {
+        let mut proximo = self.next.take();
+        while let Some(mut node) = proximo {
+            proximo = node.next.take();
+        }
+    }
+}
+
+/// # Iterador sobre a lista encadeada de um vértice, devolvendo os pares (vizinho, peso) sem alocar.
+
+pub struct NeighborIter<'a, W> {
+    head: Option<&'a Node<W>>,
+}
+
+impl<'a, W> NeighborIter<'a, W> {
+
+    /// # Cria o iterador a partir do primeiro nó da lista (ou None para uma lista vazia).
+
+    pub fn new(head: Option<&'a Node<W>>) -> Self {
+        NeighborIter { head }
+    }
+}
+
+impl<W: Copy> Iterator for NeighborIter<'_, W> {
+    type Item = (usize, W);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.head?;
+        self.head = node.next.as_deref();
+        Some((node.value, node.weight))
+    }
+}
+
+/// # Política para arestas paralelas (mais de uma aresta entre a mesma origem e o mesmo destino).
+/// ## Variantes
+/// "KeepAll" - Guarda todas as arestas, é o comportamento de um multigrafo (padrão);\
+/// "KeepMin" - Guarda só a aresta de menor peso;\
+/// "KeepLast" - Guarda só a última aresta incluída;\
+/// "Reject" - Recusa a aresta repetida com um erro.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParallelEdges {
+    #[default]
+    KeepAll,
+    KeepMin,
+    KeepLast,
+    Reject,
+}
+
+/// # Arestas guardadas durante a leitura de um arquivo com KeepMin, KeepLast ou Reject (veja o edge_na_leitura()).
+/// ## Atributos
+/// "indice" - Posição em "arestas" de cada par já lido (em grafos não-direcionados o par é (menor, maior));\
+/// "arestas" - Origem, destino e atributos de cada par, na ordem da primeira vez que ele apareceu e com a política já aplicada.
+
+#[derive(Debug)]
+struct Leitura<W> {
+    indice: HashMap<(usize, usize), usize>,
+    arestas: Vec<(usize, usize, Vec<W>)>,
+}
+
 /// # Representa o grafo inteiro.
 /// ## Atributos
-/// "is_zero_based" - Booleano paraque indica grafos que originalmente não são 0-based, para na hora de mostrar o grafo mostrar os valores dos vértices especificados.\
+/// "is_zero_based" - Booleano paraque indica grafos que originalmente não são 0-based, para na hora de mostrar o grafo mostrar os valores dos vértices especificados (read_graph_with() grava aqui a indexação escolhida).\
 /// "num_vertex" - Valor da quatidade de vértices do grafo (sempre representado por um inteiro positivo);\
-/// "num_edges" - Valor da quantidade de arestas do grafo (sempre repesentado por um inteiro positivo);\
-/// "adj" - HashMap dos vértices do grafo.
+/// "is_directed" - Booleano que indica se as arestas têm direção. Em grafos não-direcionados cada aresta é guardada nas duas listas de adjacência (assim os algoritmos percorrem os dois sentidos), mas conta uma vez só;\
+/// "num_edges" - Valor da quantidade de arestas presentes no grafo (sempre repesentado por um inteiro positivo, atualizado pelas funções de inclusão e remoção);\
+/// "declared_edges" - Quantidade de arestas declarada no cabeçalho do arquivo lido (None se o grafo não veio de um arquivo com cabeçalho). Não muda com inclusões e remoções;\
+/// "adj" - BTreeMap dos vértices do grafo, ordenado pelo vértice;\
+/// "labels" - Tabela opcional com o nome dos vértices (None quando o arquivo não declara nomes);\
+/// "attribute_names" - Nome de cada atributo das arestas, o primeiro é o do peso ("weight" por padrão);\
+/// "parallel_edges" - O que fazer quando uma aresta repetida é incluída (KeepAll por padrão).\
+/// O tipo do peso "W" é genérico (i32 por padrão), veja o trait Weight.
 
 #[derive(Debug)]
-pub struct Graph {
+pub struct Graph<W = i32> {
     pub is_zero_based : bool,
+    pub is_directed: bool,
     pub num_vertex: usize,
     pub num_edges: usize,
-    pub adj: HashMap<usize, Option<Box<Node>>>,
+    pub declared_edges: Option<usize>,
+    pub adj: BTreeMap<usize, Option<Box<Node<W>>>>,
+    pub labels: Option<Labels>,
+    pub attribute_names: Vec<String>,
+    pub parallel_edges: ParallelEdges,
+    leitura: Option<Leitura<W>>, // Só existe entre o primeiro edge_na_leitura() e o terminar_leitura()
 }
 
-impl Graph {
+impl<W: Weight> Graph<W> {
 
     /// # Função que retorna o número de vérfices do grafo
 
@@ -86,36 +193,426 @@ impl Graph {
         self.num_vertex
     }
 
-    /// # Função que cria um novo grafo
+    /// # Função que cria um novo grafo direcionado, sem arestas
 
-    pub fn new(is_zero_based: bool, num_vertex: usize, num_edges: usize) -> Self {
-        Graph {is_zero_based, num_vertex, num_edges, adj: HashMap::new()}
+    pub fn new(is_zero_based: bool, num_vertex: usize) -> Self {
+        Graph {is_zero_based, is_directed: true, num_vertex, num_edges: 0, declared_edges: None, adj: BTreeMap::new(), labels: None, attribute_names: vec!["weight".to_string()], parallel_edges: ParallelEdges::KeepAll, leitura: None}
+    }
+
+    /// # Função que cria um novo grafo não-direcionado, sem arestas
+
+    pub fn new_undirected(is_zero_based: bool, num_vertex: usize) -> Self {
+        let mut graph = Graph::new(is_zero_based, num_vertex);
+        graph.is_directed = false;
+        graph
+    }
+
+    /// # Função que cria um novo grafo, sem arestas, cujas arestas têm os atributos nomeados (o primeiro é o peso)
+    /// ```rust
+    ///    let gr: Graph<f64> = Graph::with_attributes(true, 7, &["distance", "time", "energy"]);
+    /// ```
+
+    pub fn with_attributes(is_zero_based: bool, num_vertex: usize, nomes: &[&str]) -> Self {
+        assert!(!nomes.is_empty(), "An edge needs at least one attribute.");
+        let mut graph = Graph::new(is_zero_based, num_vertex);
+        graph.attribute_names = nomes.iter().map(|nome| nome.to_string()).collect();
+        graph
+    }
+
+    /// # Retorna o índice do atributo com esse nome, se existir
+
+    pub fn attribute_index(&self, nome: &str) -> Option<usize> {
+        self.attribute_names.iter().position(|atributo| atributo == nome)
     }
 
     /// # Função para adicionar novas arestas no grafo
+    /// Em grafos não-direcionados a aresta vale nos dois sentidos.\
+    /// Se o grafo tiver mais de um atributo, os demais ficam com zero; use edge_with_attributes() para informar todos.
+    /// ## Retorno
+    /// Erro apenas se a aresta já existir e a política de arestas paralelas for Reject.
+
+    pub fn edge(&mut self, origin: usize, destination: usize, destination_weight: W) -> Result<(), GraphError> {
+        let mut atributos = vec![W::zero(); self.attribute_names.len()];
+        atributos[0] = destination_weight;
+        self.edge_with_attributes(origin, destination, &atributos)
+    }
 
-    pub fn edge(&mut self, origin: usize, destination: usize, destination_weight: i32) {
+    /// # Função para adicionar uma aresta com todos os atributos, na ordem de attribute_names
+    /// ## Arestas paralelas
+    /// Se a aresta já existir, a política "parallel_edges" decide:
+    /// ```rust
+    ///    ParallelEdges::KeepAll => // inclui mais uma aresta
+    ///    ParallelEdges::KeepMin => // troca os atributos se o peso novo for menor
+    ///    ParallelEdges::KeepLast => // troca os atributos pelos novos
+    ///    ParallelEdges::Reject => // retorna GraphError::DuplicateEdge
+    /// ```
+
+    pub fn edge_with_attributes(&mut self, origin: usize, destination: usize, atributos: &[W]) -> Result<(), GraphError> {
+        assert_eq!(atributos.len(), self.attribute_names.len(), "The edge must have one value for each attribute.");
+
+        if !self.aresta_repetida(origin, destination, atributos)? {
+            self.inserir(origin, destination, atributos);
+        }
+        Ok(())
+    }
+
+    /// # Aplica a política de arestas paralelas se a aresta já existir
+    /// ## Retorno
+    /// true se a aresta já existia (e a política já decidiu o que fazer), false se ela ainda precisa ser incluída.
+
+    fn aresta_repetida(&mut self, origin: usize, destination: usize, atributos: &[W]) -> Result<bool, GraphError> {
+        if self.parallel_edges == ParallelEdges::KeepAll {
+            return Ok(false);
+        }
+        let Some(peso_atual) = self.weight(&origin, &destination) else {
+            return Ok(false);
+        };
+        match self.parallel_edges {
+            ParallelEdges::Reject => return Err(GraphError::DuplicateEdge { origin, destination }),
+            ParallelEdges::KeepMin if atributos[0] < peso_atual => self.overwrite_edge(origin, destination, atributos),
+            ParallelEdges::KeepLast => self.overwrite_edge(origin, destination, atributos),
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    /// # Inclui a aresta em um grafo com KeepAll sem o Result do edge_with_attributes()
+    /// Com KeepAll a política nunca recusa nem substitui uma aresta, então pular a consulta dá exatamente o mesmo grafo. É o caso de todos os chamadores:\
+    /// As operações (transpose(), edge_subgraph(), union()) copiam as arestas em um grafo novo, que nasce com KeepAll, e só copiam a política depois;\
+    /// Os geradores criam o grafo e nunca trocam a política.\
+    /// Em um grafo com outra política entra em pânico, em vez de guardar uma aresta paralela que a política recusaria.
+
+    pub(crate) fn incluir_aresta(&mut self, origin: usize, destination: usize, atributos: &[W]) {
+        assert_eq!(self.parallel_edges, ParallelEdges::KeepAll, "Only KeepAll graphs may skip the parallel-edge policy.");
+        self.inserir(origin, destination, atributos);
+    }
+
+    /// # Coloca a aresta no fim das listas e conta ela, depois que a política já foi consultada
+
+    fn inserir(&mut self, origin: usize, destination: usize, atributos: &[W]) {
+        debug_assert_eq!(atributos.len(), self.attribute_names.len(), "The edge must have one value for each attribute.");
+        self.push_node(origin, Node::with_attributes(destination, atributos));
+        if !self.is_directed && origin != destination {
+            self.push_node(destination, Node::with_attributes(origin, atributos)); // sentido contrário
+        }
+        self.num_edges += 1;
+    }
+
+    /// # Mesmo que edge_with_attributes(), mas em O(1) com qualquer política de arestas paralelas
+    /// Usada pelos leitores de arquivo, que começam de um grafo sem arestas, para a leitura ser O(V + E) e não O(E * grau):\
+    /// Com KeepAll a aresta entra no começo das listas em vez de no fim, sem percorrê-las;\
+    /// Com KeepMin, KeepLast e Reject a aresta fica em um índice por par (HashMap), então a repetida é achada sem consultar o weight(), e só entra nas listas no terminar_leitura().\
+    /// As listas só ficam prontas (e na ordem das linhas lidas) no terminar_leitura(), que precisa ser chamado depois da última aresta.
+
+    pub(crate) fn edge_na_leitura(&mut self, origin: usize, destination: usize, atributos: &[W]) -> Result<(), GraphError> {
+        assert_eq!(atributos.len(), self.attribute_names.len(), "The edge must have one value for each attribute.");
+
+        let politica = self.parallel_edges;
+        if politica == ParallelEdges::KeepAll {
+            self.incluir_na_leitura(origin, destination, atributos);
+            return Ok(());
+        }
+
+        let par = if self.is_directed { (origin, destination) } else { (origin.min(destination), origin.max(destination)) };
+        let leitura = self.leitura.get_or_insert_with(|| Leitura { indice: HashMap::new(), arestas: Vec::new() });
+        match leitura.indice.get(&par) {
+            None => {
+                leitura.indice.insert(par, leitura.arestas.len());
+                leitura.arestas.push((origin, destination, atributos.to_vec()));
+            }
+            Some(&posicao) => {
+                let atual = &mut leitura.arestas[posicao].2;
+                match politica {
+                    ParallelEdges::Reject => return Err(GraphError::DuplicateEdge { origin, destination }),
+                    ParallelEdges::KeepMin if atributos[0] < atual[0] => *atual = atributos.to_vec(),
+                    ParallelEdges::KeepLast => *atual = atributos.to_vec(),
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// # Mesmo que inserir(), mas no começo das listas (veja o edge_na_leitura())
+
+    pub(crate) fn incluir_na_leitura(&mut self, origin: usize, destination: usize, atributos: &[W]) {
+        self.push_front(origin, Node::with_attributes(destination, atributos));
+        if !self.is_directed && origin != destination {
+            self.push_front(destination, Node::with_attributes(origin, atributos)); // sentido contrário
+        }
+        self.num_edges += 1;
+    }
+
+    /// # Inclui as arestas que ficaram no índice e desinverte as listas montadas pelo edge_na_leitura(), deixando os vizinhos na ordem em que as arestas foram lidas
+
+    pub(crate) fn terminar_leitura(&mut self) {
+        if let Some(leitura) = self.leitura.take() {
+            for (origem, destino, atributos) in leitura.arestas {
+                self.incluir_na_leitura(origem, destino, &atributos);
+            }
+        }
+        for lista in self.adj.values_mut() {
+            let mut invertida: Option<Box<Node<W>>> = None;
+            let mut atual = lista.take();
+            while let Some(mut node) = atual {
+                atual = node.next.take();
+                node.next = invertida;
+                invertida = Some(node);
+            }
+            *lista = invertida;
+        }
+    }
+
+    /// # Adiciona uma aresta que vale nos dois sentidos
+    /// Em grafos não-direcionados é o mesmo que edge(); em grafos direcionados adiciona as duas arestas (origem, destino) e (destino, origem).
+
+    pub fn add_undirected_edge(&mut self, a: usize, b: usize, peso: W) -> Result<(), GraphError> {
+        self.edge(a, b, peso)?;
+        if self.is_directed && a != b {
+            self.edge(b, a, peso)?;
+        }
+        Ok(())
+    }
+
+    /// # Retorna todas as arestas (paralelas) de origem para destino, na ordem em que foram incluídas
+
+    pub fn parallel_edges(&self, origem: usize, destino: usize) -> impl Iterator<Item = &Node<W>> {
+        let head = self.adj.get(&origem).and_then(|lista| lista.as_deref());
+        std::iter::successors(head, |node| node.next.as_deref()).filter(move |node| node.value == destino)
+    }
+
+    /// # Troca os atributos da primeira aresta de origem para destino (e do sentido contrário em grafos não-direcionados)
+
+    fn overwrite_edge(&mut self, origem: usize, destino: usize, atributos: &[W]) {
+        for (de, para) in [(origem, destino), (destino, origem)] {
+            let mut head = self.adj.get_mut(&de).and_then(|lista| lista.as_mut());
+            while let Some(node) = head {
+                if node.value == para {
+                    node.weight = atributos[0];
+                    node.extra = atributos[1..].to_vec();
+                    break;
+                }
+                head = node.next.as_mut();
+            }
+            if self.is_directed || origem == destino {
+                break;
+            }
+        }
+    }
+
+    /// # Coloca o nó no fim da lista de vizinhos da origem
+    /// Percorre a lista inteira (O(grau)), então os leitores de arquivo usam o push_front() e desinvertem as listas no final (edge_na_leitura()).
+
+    fn push_node(&mut self, origin: usize, node: Node<W>) {
         // Use `entry` para garantir que a origem tem um valor válido
         let head = self.adj.entry(origin).or_insert(None); //pega a lista de vizinhos de origin ou None
         match head { //adiciona destination como vizinho final
-            Some(node) => node.append(destination, destination_weight),
-            None => *head = Some(Box::new(Node::new(destination, destination_weight))),
+            Some(first) => first.append_node(node),
+            None => *head = Some(Box::new(node)),
         }
     }
 
-    /// # Função para printar o grafo
+    /// # Coloca o nó no começo da lista de vizinhos da origem, sem percorrer a lista
 
-    pub fn print(&self) {
-        for (v, list) in &self.adj {
-            if self.is_zero_based == false{
-                print!("{} -> ", v + 1);
+    fn push_front(&mut self, origin: usize, mut node: Node<W>) {
+        let head = self.adj.entry(origin).or_insert(None);
+        node.next = head.take();
+        *head = Some(Box::new(node));
+    }
+
+    /// # Remove a aresta de origem para destino
+    /// Se existirem arestas repetidas, remove apenas a primeira. Em grafos não-direcionados remove os dois sentidos.
+    /// ## Retorno
+    /// O peso da aresta removida, ou None se ela não existir.
+
+    pub fn remove_edge(&mut self, origem: usize, destino: usize) -> Option<W> {
+        let removido = self.remove_node(origem, destino)?;
+        if !self.is_directed && origem != destino {
+            self.remove_node(destino, origem);
+        }
+        self.num_edges -= 1;
+
+        Some(removido.weight)
+    }
+
+    /// # Tira da lista de origem o primeiro nó com o destino, sem mexer nos contadores
+
+    fn remove_node(&mut self, origem: usize, destino: usize) -> Option<Box<Node<W>>> {
+        let lista = self.adj.get_mut(&origem)?;
+
+        // anda até o ponteiro que aponta para o nó com o destino
+        let mut atual = lista;
+        while atual.as_ref().is_some_and(|node| node.value != destino) {
+            atual = &mut atual.as_mut()?.next;
+        }
+
+        // religa a lista sem o nó removido
+        let mut removido = atual.take()?;
+        *atual = removido.next.take();
+
+        if self.adj.get(&origem).is_some_and(|lista| lista.is_none()) {
+            self.adj.remove(&origem); // vértice sem vizinhos não fica no mapa
+        }
+
+        Some(removido)
+    }
+
+    /// # Troca o peso da aresta de origem para destino (nos dois sentidos em grafos não-direcionados)
+    /// ## Retorno
+    /// O peso antigo, ou None se a aresta não existir (nesse caso nada é alterado).
+
+    pub fn set_weight(&mut self, origem: usize, destino: usize, peso: W) -> Option<W> {
+        let antigo = self.set_node_weight(origem, destino, peso)?;
+        if !self.is_directed && origem != destino {
+            self.set_node_weight(destino, origem, peso);
+        }
+        Some(antigo)
+    }
+
+    /// # Troca o peso do primeiro nó com o destino na lista de origem
+
+    fn set_node_weight(&mut self, origem: usize, destino: usize, peso: W) -> Option<W> {
+        let mut head = self.adj.get_mut(&origem)?.as_mut();
+
+        while let Some(node) = head {
+            if node.value == destino {
+                return Some(std::mem::replace(&mut node.weight, peso));
             }
-            else{
-                print!("{} -> ", v);
+            head = node.next.as_mut();
+        }
+
+        None
+    }
+
+    /// # Remove um vértice e todas as arestas que chegam ou saem dele
+    /// Para os algoritmos continuarem usando vetores de tamanho num_vertex, os vértices seguintes são renumerados: quem era "vertice + 1" passa a ser "vertice" e assim por diante.
+    /// ## Exemplo
+    /// Removendo o vértice 1 do grafo 0 -> 1 -> 2 -> 3, as arestas (0, 1) e (1, 2) somem e sobra só 1 -> 2 (a antiga aresta 2 -> 3, já que o antigo 2 virou 1 e o antigo 3 virou 2). O vértice 0 fica sem arestas.
+    /// ## Retorno
+    /// A quantidade de arestas removidas, ou None se o vértice não existir.
+
+    pub fn remove_vertex(&mut self, vertice: usize) -> Option<usize> {
+        if vertice >= self.num_vertex {
+            return None;
+        }
+
+        let mut removidas = 0;
+        let mut removidas_entrada = 0;
+
+        // arestas que saem do vértice
+        if let Some(lista) = self.adj.remove(&vertice) {
+            removidas += NeighborIter::new(lista.as_deref()).count();
+        }
+
+        // arestas que chegam no vértice e renumeração dos vizinhos
+        for lista in self.adj.values_mut() {
+            let mut atual = lista;
+            loop {
+                if atual.as_ref().is_some_and(|node| node.value == vertice) {
+                    if let Some(mut removido) = atual.take() {
+                        *atual = removido.next.take();
+                        removidas_entrada += 1;
+                    }
+                    continue;
+                }
+                match atual {
+                    Some(node) => {
+                        if node.value > vertice {
+                            node.value -= 1;
+                        }
+                        atual = &mut node.next;
+                    }
+                    None => break,
+                }
             }
-            if let Some(node) = list {
-                node.print(self.is_zero_based);
+        }
+
+        // renumeração das origens, descartando as listas que ficaram vazias
+        self.adj = std::mem::take(&mut self.adj)
+            .into_iter()
+            .filter(|(_, lista)| lista.is_some())
+            .map(|(v, lista)| if v > vertice { (v - 1, lista) } else { (v, lista) })
+            .collect();
+
+        if let Some(labels) = &mut self.labels {
+            labels.remove_vertex(vertice);
+        }
+
+        // em grafos não-direcionados as arestas que chegam são o outro sentido das que saem
+        if self.is_directed {
+            removidas += removidas_entrada;
+        }
+
+        self.num_vertex -= 1;
+        self.num_edges -= removidas;
+
+        Some(removidas)
+    }
+
+    /// # Dá um nome ao vértice (0-based), criando a tabela de nomes se ela ainda não existir
+
+    pub fn set_label(&mut self, vertice: usize, nome: &str) {
+        self.labels.get_or_insert_with(Labels::new).insert(vertice, nome);
+    }
+
+    /// # Retorna o nome do vértice, se existir
+
+    pub fn label(&self, vertice: usize) -> Option<&str> {
+        self.labels.as_ref()?.name(vertice)
+    }
+
+    /// # Retorna o vértice (0-based) com esse nome, se existir
+
+    pub fn vertex_by_label(&self, nome: &str) -> Option<usize> {
+        self.labels.as_ref()?.id(nome)
+    }
+
+    /// # Retorna como o vértice deve ser mostrado: o nome, se existir, ou o número como está no arquivo (somando 1 em grafos 1-based)
+
+    pub fn vertex_name(&self, vertice: usize) -> String {
+        match self.label(vertice) {
+            Some(nome) => nome.to_string(),
+            None if self.is_zero_based => vertice.to_string(),
+            None => (vertice + 1).to_string(),
+        }
+    }
+
+    /// # Formata um caminho (como o de reconstruir_caminho()) no formato [a, b, c], usando o nome dos vértices
+
+    pub fn format_path(&self, caminho: &[usize]) -> String {
+        let nomes: Vec<String> = caminho.iter().map(|v| self.vertex_name(*v)).collect();
+        format!("[{}]", nomes.join(", "))
+    }
+
+    /// # Formata os atributos de uma aresta, por exemplo "weight: 5" ou "distance: 5, time: 3"
+
+    fn format_attributes(&self, node: &Node<W>) -> String {
+        formatar_atributos(&self.attribute_names, |i| node.attribute(i).unwrap_or(W::zero()))
+    }
+
+    /// # Diminui em 1 todos os vértices, usado pelo read_graph_from() quando só descobre no fim que o arquivo era 1-based
+
+    fn deslocar_vertices(&mut self) {
+        let adj = std::mem::take(&mut self.adj);
+        for (origem, mut lista) in adj {
+            let mut head = lista.as_deref_mut();
+            while let Some(node) = head {
+                node.value -= 1;
+                head = node.next.as_deref_mut();
             }
+            self.adj.insert(origem - 1, lista);
+        }
+    }
+
+    /// # Função para printar o grafo
+
+    pub fn print(&self) {
+        for (v, list) in &self.adj {
+            print!("{} -> ", self.vertex_name(*v));
+            let vizinhos: Vec<String> = std::iter::successors(list.as_deref(), |node| node.next.as_deref())
+                .map(|node| format!("{} ({})", self.vertex_name(node.value), self.format_attributes(node)))
+                .collect();
+            print!("{}", vizinhos.join(" -> "));
             println!();
         }
     }
@@ -123,41 +620,64 @@ impl Graph {
     /// # Retorna os vétices em uma lista
     
     pub fn vertices_list(&self) -> Vec<usize> {
-        (0..=self.num_vertex).collect() //coleta e retorna o vetor (intervalo fechado) <- REVER
+        (0..self.num_vertex).collect() //coleta e retorna o vetor de 0 até num_vertex - 1
     }
 
-    /// # Retorna uma lsita de vizinhos de um vertice
-    
-    pub fn vizinhos(&self, vertice: &usize) -> Vec<usize> {
-        let mut vizinhos = vec![];
+    /// # Retorna os pares (vizinho, peso) das arestas que saem do vértice, emprestando a lista encadeada sem alocar
+    /// Arestas paralelas aparecem uma vez cada, na ordem em que foram incluídas.
 
-        let mut head = match self.adj.get(vertice) {
-            Some(node) => node.as_ref(),
-            None => return vizinhos, // retorna vetor vazio se não houver vizinhos
-        };
+    pub fn neighbors_with_weights(&self, vertice: usize) -> NeighborIter<'_, W> {
+        NeighborIter::new(self.adj.get(&vertice).and_then(|lista| lista.as_deref()))
+    }
 
-        while let Some(node) = head {
-            vizinhos.push(node.value);
-            head = node.next.as_ref();
-        }
+    /// # Retorna os trios (origem, destino, peso) de todas as arestas, sem alocar
+    /// Em grafos não-direcionados cada aresta aparece uma vez só (com origem <= destino), então a quantidade é sempre num_edges.
 
-        vizinhos
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, W)> + '_ {
+        self.arestas_unicas().map(|(origem, node)| (origem, node.value, node.weight))
+    }
+
+    /// # Retorna os pares (origem, nó) de todas as arestas, com os atributos completos no nó
+    /// Mesma ordem e mesma regra do edges(): em grafos não-direcionados a aresta (a, b) também está guardada como (b, a), então só o sentido com origem <= destino aparece.
+
+    pub(crate) fn arestas_unicas(&self) -> impl Iterator<Item = (usize, &Node<W>)> + '_ {
+        self.adj.iter().flat_map(move |(&origem, lista)| {
+            std::iter::successors(lista.as_deref(), |node| node.next.as_deref())
+                .filter(move |node| self.is_directed || node.value >= origem)
+                .map(move |node| (origem, node))
+        })
+    }
+
+    /// # Retorna os pares (origem, peso) das arestas que chegam no vértice, sem alocar
+    /// Como só a lista de saída é guardada, percorre todas as arestas (O(V + E)) em grafos direcionados. Em grafos não-direcionados é o mesmo que neighbors_with_weights().
+
+    pub fn in_neighbors(&self, vertice: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        let todas = self.is_directed.then(|| {
+            self.adj.iter().flat_map(move |(&origem, lista)| {
+                NeighborIter::new(lista.as_deref()).filter(move |&(destino, _)| destino == vertice).map(move |(_, peso)| (origem, peso))
+            })
+        });
+        let proprias = (!self.is_directed).then(|| self.neighbors_with_weights(vertice));
+        todas.into_iter().flatten().chain(proprias.into_iter().flatten())
+    }
+
+    /// # Retorna uma lsita de vizinhos de um vertice
+    
+    pub fn vizinhos(&self, vertice: &usize) -> Vec<usize> {
+        self.neighbors_with_weights(*vertice).map(|(vizinho, _)| vizinho).collect()
     }
 
     /// # Retorna o vizinho mais próximo do vértice
     
     pub fn vizinho_mais_perto(&self, vertice: &usize) -> Option<usize> {
-        let mut distancia_minima = i32::MAX;
+        let mut distancia_minima: Option<W> = None;
         let mut escolhido = None;
 
-        let mut head = self.adj.get(vertice)?.as_ref(); // pega a lista encadeada dos vizinhos do vértice
-
-        while let Some(node) = head {
-            if node.weight < distancia_minima {
-                distancia_minima = node.weight;
-                escolhido = Some(node.value);
+        for (vizinho, peso) in self.neighbors_with_weights(*vertice) {
+            if distancia_minima.is_none_or(|d| peso < d) {
+                distancia_minima = Some(peso);
+                escolhido = Some(vizinho);
             }
-            head = node.next.as_ref();
         }
 
         escolhido
@@ -165,122 +685,458 @@ impl Graph {
 
     /// # Retorna o peso de uma aresta
     
-    pub fn weight(&self, origem: &usize, destino: &usize) -> Option<i32>{
+    pub fn weight(&self, origem: &usize, destino: &usize) -> Option<W>{
+        self.neighbors_with_weights(*origem)
+            .find(|(vizinho, _)| vizinho == destino)
+            .map(|(_, peso)| peso)
+    }
 
-        let mut head = match self.adj.get(origem) {
-            Some(node) => node.as_ref(),
-            None => return None,
-        };
+}
 
-        while let Some(node) = head {
-            if node.value == *destino {
-                return Some(node.weight);
-            }
-            head = node.next.as_ref();
-        }
+/// # Formata os atributos de uma aresta a partir do valor de cada um (0 é o peso), usado pelo print() do Graph e do CsrGraph
 
-        None
-    }
+pub(crate) fn formatar_atributos<W: Weight>(nomes: &[String], valor: impl Fn(usize) -> W) -> String {
+    let atributos: Vec<String> = nomes
+        .iter()
+        .enumerate()
+        .map(|(i, nome)| format!("{}: {}", nome, valor(i)))
+        .collect();
+    atributos.join(", ")
+}
+
+/// # Indexação dos vértices no arquivo txt.
+/// ## Variantes
+/// "ZeroBased" - Os vértices do arquivo já começam em 0 (como o "graph2.txt");\
+/// "OneBased" - Os vértices do arquivo começam em 1 (como o "graph1.txt") e são ajustados para 0-based;\
+/// "Auto" - Heurística antiga: se algum vértice 0 aparecer nas arestas o arquivo é 0-based, senão é 1-based;\
+/// "Remap" - Os vértices do arquivo são ids quaisquer (100234, 5000017, "SE-04"...): cada id novo vira o próximo vértice (0, 1, 2...) e o id original vira o nome do vértice.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Indexing {
+    ZeroBased,
+    OneBased,
+    #[default]
+    Auto,
+    Remap,
+}
+
+/// # Opções de leitura do grafo.
+/// ## Atributos
+/// "indexing" - Como os vértices estão numerados no arquivo (Auto por padrão);\
+/// "parallel_edges" - Política para arestas repetidas no arquivo (KeepAll por padrão), fica gravada no grafo lido.
+/// ## Exemplo
+/// ```rust
+///    let options = ReadOptions { indexing: Indexing::OneBased, parallel_edges: ParallelEdges::KeepMin };
+///    let gr: Graph = read_graph_with("data/graph1.txt", &options)?;
+/// ```
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReadOptions {
+    pub indexing: Indexing,
+    pub parallel_edges: ParallelEdges,
+}
+
+/// # Função que lê o grafo a partir de um arquivo txt, detectando a indexação automaticamente.
+/// Equivale a read_graph_with() com as opções padrão, veja a documentação dela.
 
+pub fn read_graph<W: Weight>(path: &str) -> Result<Graph<W>, GraphError> {
+    read_graph_with(path, &ReadOptions::default())
 }
 
 /// # Função que lê o grafo a partir de um arquivo txt.
 /// ## Argumentos
 /// 
 /// "path" - A string do caminho do txt do grafo na formatação:\
-/// <num_vertices> <num_arestas>\
-/// <vertice_inicial> <vertice_final> <custo> <- Repetição para cada aresta
+/// <num_vertices> <num_arestas> [directed | undirected] <- A direção é opcional, o padrão é direcionado\
+/// <vertice_inicial> <vertice_final> <custo> <- Repetição para cada aresta\
+/// "options" - As opções de leitura (indexação dos vértices).
+///
+/// ## Grafos não-direcionados
+/// Com "undirected" no cabeçalho cada aresta é escrita uma vez só no arquivo (e conta uma vez em <num_arestas>), mas vale nos dois sentidos.
+///
+/// ## Arestas com vários atributos
+/// Logo depois do cabeçalho o arquivo pode declarar os nomes dos atributos das arestas. Cada aresta passa a ter uma coluna para cada atributo, e o primeiro vira o peso:\
+/// attrs <atributo_1> <atributo_2> ... <- Por exemplo "attrs distance time energy"\
+/// <vertice_inicial> <vertice_final> <atributo_1> <atributo_2> ...
+///
+/// ## Nomes dos vértices
+/// Em qualquer linha depois do cabeçalho o arquivo pode declarar o nome de um vértice, usando a mesma indexação das arestas. Essas linhas não contam como arestas:\
+/// v <vertice> <nome> <- O nome vai até o fim da linha e pode ter espaços, por exemplo "v 1 Praça da Sé"
 ///
 /// ## Retorno
 /// 
-/// O grafo completo com um HashMap ou o erro associado a criação.
+/// O grafo completo com um BTreeMap ou o erro associado a criação.
+/// ## Erros
+/// A leitura é estrita: o primeiro token inválido, linha com quantidade errada de colunas, vértice fora do intervalo declarado ou diferença entre a quantidade de arestas do cabeçalho e do arquivo gera um GraphError com o arquivo, a linha e a coluna do problema. Linhas em branco são ignoradas.
+/// ## Tipo do peso
+/// O custo é lido no tipo de peso escolhido pelo chamador (qualquer tipo que implemente Weight):
+/// ```rust
+///    let gr: Graph<f64> = read_graph("data/graph1.txt")?;
+/// ```
 /// ## Indexação
-/// Como estamos utilizando um HashMap precisamos ajustar para vértices 0-based. Como o cenário 2 já está nesse padrão, precisamos criar uma função de detecção.
+/// Como estamos utilizando um mapa indexado pelo vértice precisamos ajustar para vértices 0-based. O chamador declara a indexação do arquivo em "options.indexing"; só no modo Auto usamos a detecção:
 /// ```rust
-///    let mut is_zero_indexed = false;
-///    for aresta_chunk in arestas_data.chunks(3) {
-///        if aresta_chunk.len() == 3 {
-///            if aresta_chunk[0] == 0 || aresta_chunk[1] == 0 {
-///                is_zero_indexed = true;
-///                break; 
-///            }
-///        }
-///    }
+///    let is_zero_indexed = arestas.iter().any(|&(origem, destino, _)| origem == 0 || destino == 0);
 /// ```
 /// ...
 /// ```rust
-///     let origem = if is_zero_indexed {
-///                origem_raw as usize
-///            } else {
-///                (origem_raw - 1) as usize
-///            };
+///     let (origem, destino) = if is_zero_indexed {
+///         (origem_raw, destino_raw)
+///     } else {
+///         (origem_raw - 1, destino_raw - 1)
+///     };
+/// ```
+/// Em um arquivo declarado 1-based, um vértice 0 é um erro de dado inválido.
+/// ## Ids esparsos
+/// Com Indexing::Remap os vértices podem ser qualquer token sem espaços, e os vetores dos algoritmos ficam do tamanho da quantidade de ids, não do maior id:
+/// ```rust
+///    3 2
+///    100234 5000017 7
+///    5000017 88 4
+/// ```
+/// Os ids viram os vértices 0, 1 e 2, na ordem em que aparecem, e cada id fica como nome do vértice. Assim o print(), o format_path() e os relatórios dos cenários mostram os ids originais, e vertex_by_label("5000017") retorna o vértice.\
+/// <num_vertices> passa a ser a quantidade máxima de ids diferentes. As linhas "v <id> <nome>" também usam o id original, e o nome toma o lugar do id.\
+/// Os ids são comparados como texto ("007" e "7" são vértices diferentes).\
+/// Como "v" e "attrs" também podem ser ids, as linhas "v" e "attrs" só são lidas como declarações antes da primeira aresta; depois dela toda linha é uma aresta. Um arquivo cuja primeira aresta sai do id "v" ou "attrs" precisa de outra aresta antes dela.
+pub fn read_graph_with<W: Weight>(path: &str, options: &ReadOptions) -> Result<Graph<W>, GraphError> {
+    let file = File::open(path).map_err(|e| GraphError::io(path, e))?;
+    read_graph_from(BufReader::new(file), path, options)
+}
+
+/// # Função que lê o grafo linha a linha de qualquer BufRead (arquivo, stdin, descompressor gzip...).
+/// ## Argumentos
+/// 
+/// "reader" - De onde as linhas são lidas, na mesma formatação do read_graph_with();\
+/// "path" - Nome usado nas mensagens de erro (por exemplo "<stdin>");\
+/// "options" - As opções de leitura.
 ///
-///            let destino = if is_zero_indexed {
-///                destino_raw as usize
-///            } else {
-///                (destino_raw - 1) as usize
-///            };
+/// ## Memória
+/// Cada aresta entra no grafo assim que a sua linha é lida, então além do próprio grafo só ficam na memória a linha atual e os nomes dos vértices.\
+/// No modo Indexing::Auto a indexação só é conhecida no fim do arquivo: as arestas entram com os vértices como estão no arquivo e, se nenhum vértice 0 aparecer, todos são deslocados para 0-based no final, sem copiar o grafo.
+/// ## Exemplo
+/// ```rust
+///    let stdin = io::stdin();
+///    let gr: Graph = read_graph_from(stdin.lock(), "<stdin>", &ReadOptions::default())?;
 /// ```
-pub fn read_graph(path: &str) -> Result<Graph, io::Error> {
-    let content = fs::read_to_string(path)?;
 
-    let numbers: Vec<i32> = content
-        .split_whitespace()
-        .filter_map(|palavra| palavra.parse::<i32>().ok())
-        .collect();
+pub fn read_graph_from<W: Weight, R: BufRead>(mut reader: R, path: &str, options: &ReadOptions) -> Result<Graph<W>, GraphError> {
+    let mut numero_linha = 0;
+    // Lê a próxima linha que não está em branco para o buffer, retornando o número dela
+    let mut proxima_linha = |buffer: &mut String| -> Result<Option<usize>, GraphError> {
+        loop {
+            buffer.clear();
+            if reader.read_line(buffer).map_err(|e| GraphError::io(path, e))? == 0 {
+                return Ok(None);
+            }
+            numero_linha += 1;
+            if !buffer.trim().is_empty() {
+                return Ok(Some(numero_linha));
+            }
+        }
+    };
 
-    if numbers.len() < 2 {
-        return Err(io::Error::new(io::ErrorKind::InvalidData, "Arquivo não contém dados de vértices e arestas"));
+    let mut buffer = String::new();
+    let linha_cabecalho = proxima_linha(&mut buffer)?.ok_or_else(|| GraphError::parse(path, 1, 1, ParseErrorKind::EmptyFile))?;
+    let cabecalho = tokens_com_coluna(&buffer);
+    if cabecalho.len() != 3 {
+        checar_colunas(path, linha_cabecalho, &cabecalho, 2)?;
+    }
+    let num_vertices = parse_campo::<usize>(path, linha_cabecalho, cabecalho[0], "um número de vértices")?;
+    let num_edges = parse_campo::<usize>(path, linha_cabecalho, cabecalho[1], "um número de arestas")?;
+    let coluna_num_edges = cabecalho[1].0;
+    let is_directed = match cabecalho.get(2) {
+        None | Some((_, "directed")) => true,
+        Some((_, "undirected")) => false,
+        Some(&(coluna, token)) => return Err(GraphError::parse(path, linha_cabecalho, coluna, ParseErrorKind::InvalidToken { token: token.to_string(), expected: "\"directed\" ou \"undirected\"" })),
+    };
+
+    // Indexação declarada, ou None enquanto o modo Auto não viu o arquivo todo
+    let indexacao = match options.indexing {
+        Indexing::ZeroBased | Indexing::Remap => Some(true),
+        Indexing::OneBased => Some(false),
+        Indexing::Auto => None,
+    };
+    // Modo Remap: vértice de cada id, e os ids na ordem em que apareceram
+    let remap = options.indexing == Indexing::Remap;
+    let mut ids: HashMap<String, usize> = HashMap::new();
+    let mut ids_originais: Vec<String> = Vec::new();
+    // Modo Auto: se algum vértice 0 apareceu, e a primeira posição de um vértice igual a num_vertices (inválido se o arquivo for 0-based)
+    let mut viu_zero = false;
+    let mut primeiro_n: Option<(usize, usize)> = None;
+
+    // nomes dos atributos das arestas, só o peso se o arquivo não declarar
+    let mut atributos: Vec<String> = vec!["weight".to_string()];
+    let mut viu_atributos = false;
+    // o grafo é criado na primeira aresta, quando os atributos já são conhecidos
+    let mut grafo: Option<Graph<W>> = None;
+    let novo_grafo = |atributos: &[String]| {
+        let nomes_atributos: Vec<&str> = atributos.iter().map(|nome| nome.as_str()).collect();
+        let mut graph = Graph::with_attributes(indexacao.unwrap_or(true), num_vertices, &nomes_atributos);
+        graph.is_directed = is_directed;
+        graph.parallel_edges = options.parallel_edges;
+        graph
+    };
+    let mut encontradas = 0;
+    // (linha, (coluna, vértice), nome)
+    let mut nomes: Vec<(usize, (usize, usize), String)> = Vec::new();
+    let mut nomes_usados: HashMap<String, usize> = HashMap::new();
+
+    // Token do arquivo -> (vértice como está no arquivo, vértice guardado no grafo)
+    // O vértice guardado é o próprio valor no modo Auto (checado só contra o maior intervalo possível) e o próximo vértice livre para um id novo no modo Remap
+    let mut converter = |linha: usize, (coluna, token): (usize, &str)| -> Result<(usize, usize), GraphError> {
+        if remap {
+            let vertice = match ids.get(token) {
+                Some(&vertice) => vertice,
+                None if ids_originais.len() == num_vertices => {
+                    return Err(GraphError::parse(path, linha, coluna, ParseErrorKind::TooManyIds { id: token.to_string(), num_vertex: num_vertices }));
+                }
+                None => {
+                    ids.insert(token.to_string(), ids_originais.len());
+                    ids_originais.push(token.to_string());
+                    ids_originais.len() - 1
+                }
+            };
+            return Ok((vertice, vertice));
+        }
+        let vertice_raw = parse_campo::<usize>(path, linha, (coluna, token), "um vértice")?;
+        let vertice = match indexacao {
+            Some(is_zero_indexed) => ajustar_vertice(path, linha, coluna, vertice_raw, num_vertices, is_zero_indexed),
+            None => {
+                if vertice_raw > num_vertices {
+                    return Err(GraphError::parse(path, linha, coluna, ParseErrorKind::VertexOutOfRange { vertex: vertice_raw, num_vertex: num_vertices, is_zero_based: false }));
+                }
+                viu_zero |= vertice_raw == 0;
+                if vertice_raw == num_vertices && primeiro_n.is_none() {
+                    primeiro_n = Some((linha, coluna));
+                }
+                Ok(vertice_raw)
+            }
+        }?;
+        Ok((vertice_raw, vertice))
+    };
+
+    while let Some(linha) = proxima_linha(&mut buffer)? {
+        let tokens = tokens_com_coluna(&buffer);
+        // No modo Remap "v" e "attrs" também são ids válidos, então essas linhas só são declarações antes da primeira aresta
+        let declaracao = !remap || encontradas == 0;
+        if declaracao && tokens[0].1 == "v" {
+            if tokens.len() < 3 {
+                checar_colunas(path, linha, &tokens, 3)?;
+            }
+            let vertice_raw = if remap {
+                converter(linha, tokens[1])?.0
+            } else {
+                parse_campo::<usize>(path, linha, tokens[1], "um vértice")?
+            };
+            // O nome é o resto da linha como está, para manter os espaços internos (só o começo e o fim da linha são ignorados)
+            let inicio_nome = buffer.char_indices().nth(tokens[2].0 - 1).map_or(buffer.len(), |(byte, _)| byte);
+            let nome = buffer[inicio_nome..].trim_end().to_string();
+            if nomes_usados.insert(nome.clone(), linha).is_some() {
+                return Err(GraphError::parse(path, linha, tokens[2].0, ParseErrorKind::DuplicateLabel(nome)));
+            }
+            nomes.push((linha, (tokens[1].0, vertice_raw), nome));
+            continue;
+        }
+        if declaracao && tokens[0].1 == "attrs" {
+            if encontradas > 0 || viu_atributos || tokens.len() < 2 {
+                return Err(GraphError::parse(path, linha, tokens[0].0, ParseErrorKind::MisplacedAttributes));
+            }
+            atributos = tokens[1..].iter().map(|(_, token)| token.to_string()).collect();
+            viu_atributos = true;
+            continue;
+        }
+        checar_colunas(path, linha, &tokens, 2 + atributos.len())?;
+        let (origem_raw, origem) = converter(linha, tokens[0])?;
+        let (destino_raw, destino) = converter(linha, tokens[1])?;
+        let valores = tokens[2..]
+            .iter()
+            .map(|token| parse_campo::<W>(path, linha, *token, "um peso"))
+            .collect::<Result<Vec<W>, GraphError>>()?;
+
+        let graph = grafo.get_or_insert_with(|| novo_grafo(&atributos));
+        graph.edge_na_leitura(origem, destino, &valores).map_err(|_| {
+            GraphError::parse(path, linha, tokens[0].0, ParseErrorKind::DuplicateEdge { origin: origem_raw, destination: destino_raw })
+        })?;
+        encontradas += 1;
     }
 
-    let num_vertices = numbers[0] as usize;
-    let num_edges = numbers[1] as usize;
+    if encontradas != num_edges {
+        return Err(GraphError::parse(path, linha_cabecalho, coluna_num_edges, ParseErrorKind::EdgeCountMismatch { header: num_edges, found: encontradas }));
+    }
 
-    let arestas_data = &numbers[2..];
-    
-    let mut is_zero_indexed = false;
-    for aresta_chunk in arestas_data.chunks(3) {
-        if aresta_chunk.len() == 3 {
-            if aresta_chunk[0] == 0 || aresta_chunk[1] == 0 {
-                is_zero_indexed = true;
-                break; 
+    let mut graph = grafo.unwrap_or_else(|| novo_grafo(&atributos));
+    graph.terminar_leitura();
+
+    let is_zero_indexed = match indexacao {
+        Some(is_zero_indexed) => is_zero_indexed,
+        None => {
+            let detectado = viu_zero || nomes.iter().any(|&(_, (_, vertice), _)| vertice == 0);
+            if detectado {
+                if let Some((linha, coluna)) = primeiro_n {
+                    return Err(GraphError::parse(path, linha, coluna, ParseErrorKind::VertexOutOfRange { vertex: num_vertices, num_vertex: num_vertices, is_zero_based: true }));
+                }
+            } else {
+                graph.deslocar_vertices();
+                graph.is_zero_based = false;
             }
+            detectado
+        }
+    };
+
+    // No modo Remap o id original é o nome do vértice, a não ser que uma linha "v" dê outro nome
+    for (vertice, id) in ids_originais.iter().enumerate() {
+        if !nomes.iter().any(|&(_, (_, v), _)| v == vertice) {
+            graph.set_label(vertice, id);
         }
     }
 
-    let mut graph = Graph::new(is_zero_indexed, num_vertices, num_edges);
+    for (linha, (coluna, vertice_raw), nome) in nomes {
+        let vertice = ajustar_vertice(path, linha, coluna, vertice_raw, num_vertices, is_zero_indexed)?;
+        if remap && ids.get(&nome).is_some_and(|&outro| outro != vertice && graph.label(outro) == Some(nome.as_str())) {
+            return Err(GraphError::parse(path, linha, coluna, ParseErrorKind::DuplicateLabel(nome)));
+        }
+        graph.set_label(vertice, &nome);
+    }
 
-    if is_zero_indexed {
-        println!("(Info: Grafo detectado como 0-indexado)");
-    } else {
-        println!("(Info: Grafo detectado como 1-indexado, ajustando índices)");
+    graph.declared_edges = Some(num_edges);
+    Ok(graph)
+}
+
+/// # Separa uma linha em tokens, guardando a coluna (1-based, em caracteres) onde cada um começa.
+
+pub(crate) fn tokens_com_coluna(linha: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut inicio: Option<(usize, usize)> = None; // (byte, coluna) do token atual
+
+    for (coluna, (byte, ch)) in linha.char_indices().enumerate() {
+        if ch.is_whitespace() {
+            if let Some((byte_inicio, coluna_inicio)) = inicio.take() {
+                tokens.push((coluna_inicio + 1, &linha[byte_inicio..byte]));
+            }
+        } else if inicio.is_none() {
+            inicio = Some((byte, coluna));
+        }
+    }
+    if let Some((byte_inicio, coluna_inicio)) = inicio {
+        tokens.push((coluna_inicio + 1, &linha[byte_inicio..]));
     }
 
-    for aresta_chunk in arestas_data.chunks(3) {
-        if aresta_chunk.len() == 3 {
-            let origem_raw = aresta_chunk[0];
-            let destino_raw = aresta_chunk[1];
-            let peso = aresta_chunk[2];
+    tokens
+}
 
-            let origem = if is_zero_indexed {
-                origem_raw as usize
-            } else {
-                (origem_raw - 1) as usize
-            };
+/// # Garante que a linha tem exatamente a quantidade de colunas esperada.
+/// O erro aponta para o primeiro token sobrando, ou para o fim da linha se faltar token.
 
-            let destino = if is_zero_indexed {
-                destino_raw as usize
-            } else {
-                (destino_raw - 1) as usize
-            };
-            
-            graph.edge(origem, destino, peso);
+pub(crate) fn checar_colunas(path: &str, linha: usize, tokens: &[(usize, &str)], esperado: usize) -> Result<(), GraphError> {
+    if tokens.len() == esperado {
+        return Ok(());
+    }
+    let coluna = match tokens.get(esperado) {
+        Some((coluna, _)) => *coluna,
+        None => tokens.last().map_or(1, |(coluna, token)| coluna + token.chars().count()),
+    };
+    Err(GraphError::parse(path, linha, coluna, ParseErrorKind::WrongColumnCount { expected: esperado, found: tokens.len() }))
+}
+
+/// # Converte um token do arquivo no tipo pedido, gerando um erro com a posição do token caso não seja possível.
+
+pub(crate) fn parse_campo<T: std::str::FromStr>(path: &str, linha: usize, (coluna, token): (usize, &str), esperado: &'static str) -> Result<T, GraphError> {
+    token.parse::<T>().map_err(|_| GraphError::parse(path, linha, coluna, ParseErrorKind::InvalidToken { token: token.to_string(), expected: esperado }))
+}
+
+/// # Converte o vértice do arquivo para 0-based, checando se ele está no intervalo declarado no cabeçalho.
+
+pub(crate) fn ajustar_vertice(path: &str, linha: usize, coluna: usize, vertice_raw: usize, num_vertices: usize, is_zero_indexed: bool) -> Result<usize, GraphError> {
+    let vertice = if is_zero_indexed { Some(vertice_raw) } else { vertice_raw.checked_sub(1) };
+    match vertice {
+        Some(v) if v < num_vertices => Ok(v),
+        _ => Err(GraphError::parse(path, linha, coluna, ParseErrorKind::VertexOutOfRange { vertex: vertice_raw, num_vertex: num_vertices, is_zero_based: is_zero_indexed })),
+    }
+}
+
+/// # Função que salva o grafo em um arquivo txt, na mesma formatação lida por read_graph().
+/// ## Argumentos
+/// 
+/// "graph" - O grafo a ser salvo;\
+/// "path" - O caminho do arquivo (sobrescrito se já existir).
+///
+/// ## Retorno
+/// 
+/// Nada, ou um GraphError::Io com o caminho do arquivo.
+
+pub fn write_graph<W: Weight>(graph: &Graph<W>, path: &str) -> Result<(), GraphError> {
+    let file = File::create(path).map_err(|e| GraphError::io(path, e))?;
+    let mut writer = BufWriter::new(file);
+    write_graph_to(graph, &mut writer).and_then(|_| writer.flush()).map_err(|e| GraphError::io(path, e))
+}
+
+/// # Função que escreve o grafo em qualquer destino (arquivo, stdout, Vec<u8>...) na formatação do txt.
+/// ## Formatação
+/// ```rust
+///    <num_vertices> <num_arestas> [undirected] // "undirected" só em grafos não-direcionados
+///    attrs <atributo_1> <atributo_2> ...      // só se os atributos não forem apenas "weight"
+///    v <vertice> <nome>                       // um para cada vértice com nome
+///    <vertice_inicial> <vertice_final> <atributo_1> ...
+/// ```
+/// Os vértices voltam para a indexação original (is_zero_based), e em grafos não-direcionados cada aresta é escrita uma vez só.\
+/// Um nome vazio, com espaços no começo ou no fim, ou com quebra de linha não seria lido de volta igual, e o mesmo vale para um atributo vazio ou com espaços, então os dois geram um erro de io::ErrorKind::InvalidInput.\
+/// Para ler de volta sem depender da detecção automática, use read_graph_with() com a mesma indexação do grafo salvo.
+
+pub fn write_graph_to<W: Weight, O: Write>(graph: &Graph<W>, writer: &mut O) -> io::Result<()> {
+    let ajuste = if graph.is_zero_based { 0 } else { 1 };
+
+    let mut arestas: Vec<String> = Vec::new();
+    for (origem, node) in graph.arestas_unicas() {
+        let mut colunas = vec![(origem + ajuste).to_string(), (node.value + ajuste).to_string(), node.weight.to_string()];
+        colunas.extend(node.extra.iter().map(|valor| valor.to_string()));
+        arestas.push(colunas.join(" "));
+    }
+
+    if graph.is_directed {
+        writeln!(writer, "{} {}", graph.num_vertex, arestas.len())?;
+    } else {
+        writeln!(writer, "{} {} undirected", graph.num_vertex, arestas.len())?;
+    }
+
+    if graph.attribute_names.len() > 1 || graph.attribute_names[0] != "weight" {
+        // A linha "attrs" é separada por espaços, então um nome com espaço viraria dois atributos na leitura
+        if let Some(nome) = graph.attribute_names.iter().find(|nome| !nome_de_atributo_valido(nome)) {
+            let mensagem = format!("o atributo {:?} não pode ser salvo em uma linha \"attrs\"", nome);
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, mensagem));
         }
+        writeln!(writer, "attrs {}", graph.attribute_names.join(" "))?;
     }
 
-    Ok(graph)
+    if let Some(labels) = &graph.labels {
+        for (vertice, nome) in labels.iter() {
+            // A linha "v" não teria como guardar um nome vazio, com espaços nas pontas ou com quebra de linha
+            if nome.is_empty() || nome.trim() != nome || nome.contains(['\n', '\r']) {
+                let mensagem = format!("o nome {:?} do vértice {} não pode ser salvo em uma linha \"v\"", nome, vertice + ajuste);
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, mensagem));
+            }
+            writeln!(writer, "v {} {}", vertice + ajuste, nome)?;
+        }
+    }
+
+    for aresta in arestas {
+        writeln!(writer, "{}", aresta)?;
+    }
+
+    Ok(())
 }
 
+/// # Retorna se o nome do atributo pode ir para a linha "attrs" e ser lido de volta: não vazio e sem espaços
+
+pub(crate) fn nome_de_atributo_valido(nome: &str) -> bool {
+    !nome.is_empty() && !nome.contains(char::is_whitespace)
+}
+
+/// # Representa o mapa lido do "Cenário 3": a matriz de char, as coordenadas de 'S' e as coordenadas de 'G'.
+
+pub type Mapa = (Vec<Vec<char>>, (usize, usize), (usize, usize));
+
 /// # Função que lê o mapa do "Cenário 3" a partir de um arquivo txt.
 /// ## Argumentos
 /// 
@@ -290,41 +1146,55 @@ pub fn read_graph(path: &str) -> Result<Graph, io::Error> {
 /// 
 /// Uma matriz de char igual ao mapa;\
 /// Uma tupla representando as coordenadas da matriz (0 based) do vértice de início do algoritmo ('S');\
-/// Uma tupla representando as coordenadas da matriz (0 based) do vértice de fim do algoritmo ('G');
+/// Uma tupla representando as coordenadas da matriz (0 based) do vértice de fim do algoritmo ('G');\
+/// Ou um GraphError com a linha e a coluna do problema (cabeçalho inválido, linha com tamanho diferente do declarado, quantidade de linhas errada ou 'S'/'G' ausente).
 
-pub fn read_map(path: &str) -> Result<(Vec<Vec<char>>, (usize, usize), (usize, usize)), Box<dyn Error>> {
-    let file = File::open(path)?;
+pub fn read_map(path: &str) -> Result<Mapa, GraphError> {
+    let file = File::open(path).map_err(|e| GraphError::io(path, e))?;
     let reader = BufReader::new(file);
 
     let mut lines = reader.lines();
 
-    let first_line = lines.next().ok_or("The file is empty.")??;
-    let dims: Vec<usize> = first_line
-        .split_whitespace()
-        .map(|s| s.parse())
-        .collect::<Result<_, _>>()?;
+    let first_line = lines
+        .next()
+        .ok_or_else(|| GraphError::parse(path, 1, 1, ParseErrorKind::EmptyFile))?
+        .map_err(|e| GraphError::io(path, e))?;
+    let dims = tokens_com_coluna(&first_line);
+    checar_colunas(path, 1, &dims, 2)?;
+    let rows = parse_campo::<usize>(path, 1, dims[0], "o número de linhas")?;
+    let cols = parse_campo::<usize>(path, 1, dims[1], "o número de colunas")?;
 
     let mut matrix: Vec<Vec<char>> = Vec::new();
     let mut start: Option<(usize, usize)> = None;
     let mut goal: Option<(usize, usize)> = None;
 
-    for (y, line_result) in lines.enumerate() {
-        let line = line_result?;
-        let mut char_vec: Vec<char> = Vec::new();
-        for (x, ch) in line.chars().enumerate() {
+    for (i, line_result) in lines.enumerate() {
+        let line = line_result.map_err(|e| GraphError::io(path, e))?;
+        if line.trim().is_empty() {
+            continue; // linhas em branco (como uma quebra de linha no final) não fazem parte do mapa
+        }
+        let y = matrix.len();
+        let char_vec: Vec<char> = line.chars().collect();
+        if char_vec.len() != cols {
+            return Err(GraphError::parse(path, i + 2, char_vec.len().min(cols) + 1, ParseErrorKind::WrongColumnCount { expected: cols, found: char_vec.len() }));
+        }
+        for (x, &ch) in char_vec.iter().enumerate() {
             if ch == 'S' {
                 start = Some((x, y));
             }
             if ch == 'G' {
                 goal = Some((x, y));
             }
-            char_vec.push(ch);
         }
         matrix.push(char_vec);
     }
 
-    let start_coords = start.ok_or("Start character 'S' not found in the map.")?;
-    let goal_coords = goal.ok_or("Goal character 'G' not found in the map.")?;
+    if matrix.len() != rows {
+        return Err(GraphError::parse(path, 1, dims[0].0, ParseErrorKind::RowCountMismatch { header: rows, found: matrix.len() }));
+    }
+
+    let start_coords = start.ok_or_else(|| GraphError::parse(path, 1, 1, ParseErrorKind::MissingCell('S')))?;
+    let goal_coords = goal.ok_or_else(|| GraphError::parse(path, 1, 1, ParseErrorKind::MissingCell('G')))?;
     
     Ok((matrix, start_coords, goal_coords))
 }
@@ -381,6 +1251,8 @@ pub fn get_peso(terreno: char) -> Option<i32> {
 /// ## Retorno
 /// 
 /// O arquito txt na formatação padrão
+///
+/// Para salvar qualquer outro grafo use write_graph().
 
 pub fn map_to_txt(matrix: &[Vec<char>]) -> io::Result<()> {
     let rows = matrix.len();
@@ -419,4 +1291,178 @@ pub fn map_to_txt(matrix: &[Vec<char>]) -> io::Result<()> {
     }
 
     Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// # Salva o conteúdo em um arquivo temporário (um nome por teste) e retorna o caminho, para os leitores que só abrem arquivos
+
+    pub(crate) fn arquivo_temporario(nome: &str, conteudo: &[u8]) -> String {
+        let path = std::env::temp_dir().join(format!("graph_in_rust_{}_{}", std::process::id(), nome));
+        fs::write(&path, conteudo).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    /// # Todas as arestas com todos os atributos (cada aresta não-direcionada uma vez só), para comparar grafos
+
+    pub(crate) fn arestas_com_atributos<W: Weight>(grafo: &Graph<W>) -> Vec<(usize, usize, Vec<W>)> {
+        grafo
+            .arestas_unicas()
+            .map(|(origem, node)| (origem, node.value, (0..grafo.attribute_names.len()).map(|i| node.attribute(i).unwrap_or(W::zero())).collect()))
+            .collect()
+    }
+
+    /// # Lê o texto como um arquivo "teste.txt" e retorna a posição e o tipo do erro de formatação
+
+    fn erro_de(texto: &str, indexing: Indexing, parallel_edges: ParallelEdges) -> (usize, usize, ParseErrorKind) {
+        let options = ReadOptions { indexing, parallel_edges };
+        match read_graph_from::<i32, _>(texto.as_bytes(), "teste.txt", &options) {
+            Err(GraphError::Parse(erro)) => {
+                assert_eq!(erro.file, "teste.txt");
+                (erro.line, erro.column, erro.kind)
+            }
+            outro => panic!("esperado um erro de formatação, veio {:?}", outro.map(|_| ())),
+        }
+    }
+
+    fn erro(texto: &str) -> (usize, usize, ParseErrorKind) {
+        erro_de(texto, Indexing::Auto, ParallelEdges::KeepAll)
+    }
+
+    #[test]
+    fn arquivo_vazio_ou_so_com_linhas_em_branco() {
+        assert_eq!(erro(""), (1, 1, ParseErrorKind::EmptyFile));
+        assert_eq!(erro("\n   \n\t\n"), (1, 1, ParseErrorKind::EmptyFile));
+    }
+
+    #[test]
+    fn cabecalho_com_colunas_erradas() {
+        assert_eq!(erro("5\n"), (1, 2, ParseErrorKind::WrongColumnCount { expected: 2, found: 1 }));
+        assert_eq!(erro("5 1 directed extra\n0 1 2\n"), (1, 5, ParseErrorKind::WrongColumnCount { expected: 2, found: 4 }));
+    }
+
+    #[test]
+    fn cabecalho_com_token_invalido() {
+        assert_eq!(erro("cinco 1\n"), (1, 1, ParseErrorKind::InvalidToken { token: "cinco".to_string(), expected: "um número de vértices" }));
+        assert_eq!(erro("5 -1\n"), (1, 3, ParseErrorKind::InvalidToken { token: "-1".to_string(), expected: "um número de arestas" }));
+        assert_eq!(erro("5 1 sideways\n"), (1, 5, ParseErrorKind::InvalidToken { token: "sideways".to_string(), expected: "\"directed\" ou \"undirected\"" }));
+    }
+
+    #[test]
+    fn aresta_com_colunas_erradas() {
+        // Sobrando: aponta para o primeiro token a mais. Faltando: aponta para o fim da linha
+        assert_eq!(erro("3 1\n0 1 2 3\n"), (2, 7, ParseErrorKind::WrongColumnCount { expected: 3, found: 4 }));
+        assert_eq!(erro("3 1\n0  1\n"), (2, 5, ParseErrorKind::WrongColumnCount { expected: 3, found: 2 }));
+        assert_eq!(erro("3 1\nattrs distance time\n0 1 2\n"), (3, 6, ParseErrorKind::WrongColumnCount { expected: 4, found: 3 }));
+    }
+
+    #[test]
+    fn aresta_com_vertice_invalido() {
+        assert_eq!(erro("3 1\nx 1 2\n"), (2, 1, ParseErrorKind::InvalidToken { token: "x".to_string(), expected: "um vértice" }));
+        assert_eq!(erro("3 1\n0 -1 2\n"), (2, 3, ParseErrorKind::InvalidToken { token: "-1".to_string(), expected: "um vértice" }));
+    }
+
+    #[test]
+    fn aresta_com_peso_invalido() {
+        assert_eq!(erro("3 1\n0 1 1O\n"), (2, 5, ParseErrorKind::InvalidToken { token: "1O".to_string(), expected: "um peso" }));
+        assert_eq!(erro("3 1\n0 1 1.5\n"), (2, 5, ParseErrorKind::InvalidToken { token: "1.5".to_string(), expected: "um peso" }));
+        assert_eq!(erro("3 1\nattrs distance time\n0 1 2 rápido\n"), (3, 7, ParseErrorKind::InvalidToken { token: "rápido".to_string(), expected: "um peso" }));
+    }
+
+    #[test]
+    fn colunas_contam_caracteres_e_linhas_contam_as_em_branco() {
+        assert_eq!(erro("3 1\n\n\r\n  0\t1   ç\r\n"), (4, 9, ParseErrorKind::InvalidToken { token: "ç".to_string(), expected: "um peso" }));
+        assert_eq!(erro("3 1\nv 0 São Paulo\n0 ã 1\n"), (3, 3, ParseErrorKind::InvalidToken { token: "ã".to_string(), expected: "um vértice" }));
+    }
+
+    #[test]
+    fn vertice_fora_do_intervalo() {
+        assert_eq!(erro_de("3 1\n0 3 2\n", Indexing::ZeroBased, ParallelEdges::KeepAll), (2, 3, ParseErrorKind::VertexOutOfRange { vertex: 3, num_vertex: 3, is_zero_based: true }));
+        assert_eq!(erro_de("3 1\n0 1 2\n", Indexing::OneBased, ParallelEdges::KeepAll), (2, 1, ParseErrorKind::VertexOutOfRange { vertex: 0, num_vertex: 3, is_zero_based: false }));
+        assert_eq!(erro("3 1\n1 4 2\n"), (2, 3, ParseErrorKind::VertexOutOfRange { vertex: 4, num_vertex: 3, is_zero_based: false }));
+        // No modo Auto o vértice 3 só é inválido depois que o vértice 0 mostra que o arquivo é 0-based
+        assert_eq!(erro("3 2\n1 3 2\n0 1 1\n"), (2, 3, ParseErrorKind::VertexOutOfRange { vertex: 3, num_vertex: 3, is_zero_based: true }));
+    }
+
+    #[test]
+    fn quantidade_de_arestas_diferente_do_cabecalho() {
+        assert_eq!(erro("3  2\n0 1 2\n"), (1, 4, ParseErrorKind::EdgeCountMismatch { header: 2, found: 1 }));
+        assert_eq!(erro("\n3 1\n0 1 2\n1 2 3\n"), (2, 3, ParseErrorKind::EdgeCountMismatch { header: 1, found: 2 }));
+    }
+
+    #[test]
+    fn aresta_repetida_com_reject() {
+        let texto = "3 2\n1 2 5\n1 2 7\n";
+        assert_eq!(erro_de(texto, Indexing::OneBased, ParallelEdges::Reject), (3, 1, ParseErrorKind::DuplicateEdge { origin: 1, destination: 2 }));
+        assert!(read_graph_from::<i32, _>(texto.as_bytes(), "teste.txt", &ReadOptions { indexing: Indexing::OneBased, parallel_edges: ParallelEdges::KeepMin }).is_ok());
+    }
+
+    #[test]
+    fn nomes_e_atributos_mal_colocados() {
+        assert_eq!(erro("3 1\nv 0 A\nv 1 A\n0 1 2\n"), (3, 5, ParseErrorKind::DuplicateLabel("A".to_string())));
+        assert_eq!(erro("3 1\nv 0\n0 1 2\n"), (2, 4, ParseErrorKind::WrongColumnCount { expected: 3, found: 2 }));
+        assert_eq!(erro("3 1\n0 1 2\nattrs distance\n"), (3, 1, ParseErrorKind::MisplacedAttributes));
+        assert_eq!(erro("3 1\nattrs distance\n\n  attrs time\n0 1 2\n"), (4, 3, ParseErrorKind::MisplacedAttributes));
+        assert_eq!(erro("3 1\nattrs distance time\nattrs energy\n0 1 2 3\n"), (3, 1, ParseErrorKind::MisplacedAttributes));
+        assert_eq!(erro("3 1\nattrs\n0 1 2\n"), (2, 1, ParseErrorKind::MisplacedAttributes));
+        assert_eq!(erro_de("2 2\na b 1\nb c 1\n", Indexing::Remap, ParallelEdges::KeepAll), (3, 3, ParseErrorKind::TooManyIds { id: "c".to_string(), num_vertex: 2 }));
+    }
+
+    #[test]
+    fn arquivo_valido_nao_gera_erro() {
+        let gr: Graph = read_graph_from("3 2\nv 1 Praça  da Sé\n1 2 5\n2 3 -1\n".as_bytes(), "teste.txt", &ReadOptions::default()).unwrap();
+        assert!(!gr.is_zero_based);
+        assert_eq!(gr.edges().collect::<Vec<_>>(), vec![(0, 1, 5), (1, 2, -1)]);
+        assert_eq!(gr.label(0), Some("Praça  da Sé"));
+        assert_eq!(gr.declared_edges, Some(2));
+    }
+
+    #[test]
+    fn atributo_com_espaco_nao_e_salvo() {
+        let mut gr: Graph = Graph::with_attributes(true, 2, &["energy wh", "len"]);
+        gr.edge_with_attributes(0, 1, &[3, 4]).unwrap();
+        let mut saida: Vec<u8> = Vec::new();
+        let erro = write_graph_to(&gr, &mut saida).unwrap_err();
+        assert_eq!(erro.kind(), io::ErrorKind::InvalidInput);
+
+        let mut gr: Graph = Graph::with_attributes(true, 2, &["energy_wh", "len"]);
+        gr.edge_with_attributes(0, 1, &[3, 4]).unwrap();
+        let mut saida: Vec<u8> = Vec::new();
+        write_graph_to(&gr, &mut saida).unwrap();
+        let lido: Graph = read_graph_from(saida.as_slice(), "teste.txt", &ReadOptions::default()).unwrap();
+        assert_eq!(lido.attribute_names, gr.attribute_names);
+    }
+
+    #[test]
+    fn arestas_repetidas_na_leitura_seguem_a_politica() {
+        let ler = |texto: &str, parallel_edges: ParallelEdges| -> Graph {
+            read_graph_from(texto.as_bytes(), "teste.txt", &ReadOptions { indexing: Indexing::ZeroBased, parallel_edges }).unwrap()
+        };
+        let texto = "3 4\n0 1 5\n0 2 9\n0 1 3\n0 1 4\n";
+        let gr = ler(texto, ParallelEdges::KeepMin);
+        assert_eq!(gr.edges().collect::<Vec<_>>(), vec![(0, 1, 3), (0, 2, 9)]);
+        assert_eq!(gr.num_edges, 2);
+        let gr = ler(texto, ParallelEdges::KeepLast);
+        assert_eq!(gr.edges().collect::<Vec<_>>(), vec![(0, 1, 4), (0, 2, 9)]);
+        let gr = ler(texto, ParallelEdges::KeepAll);
+        assert_eq!(gr.num_edges, 4);
+        assert_eq!(gr.neighbors_with_weights(0).collect::<Vec<_>>(), vec![(1, 5), (2, 9), (1, 3), (1, 4)]);
+
+        // Em grafos não-direcionados (1, 0) repete (0, 1), e os dois sentidos ficam com o mesmo peso
+        let gr = ler("3 3 undirected\n0 1 5\n1 2 1\n1 0 2\n", ParallelEdges::KeepMin);
+        assert_eq!(gr.edges().collect::<Vec<_>>(), vec![(0, 1, 2), (1, 2, 1)]);
+        assert_eq!(gr.weight(&1, &0), Some(2));
+        assert_eq!(gr.num_edges, 2);
+    }
+
+    #[test]
+    fn remap_aceita_v_e_attrs_como_ids_depois_da_primeira_aresta() {
+        let options = ReadOptions { indexing: Indexing::Remap, parallel_edges: ParallelEdges::KeepAll };
+        let gr: Graph = read_graph_from("4 3\nv a Praça\na v 1\nv x 2\nattrs x 3\n".as_bytes(), "teste.txt", &options).unwrap();
+        assert_eq!(gr.edges().collect::<Vec<_>>(), vec![(0, 1, 1), (1, 2, 2), (3, 2, 3)]);
+        assert_eq!([0, 1, 2, 3].map(|v| gr.label(v)), [Some("Praça"), Some("v"), Some("x"), Some("attrs")]);
+        assert_eq!(gr.attribute_names, vec!["weight".to_string()]);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,74 @@
+//! # Trait com as consultas que os algoritmos fazem no grafo
+//! ## Conteúdo:
+//! Trait de grafo com pesos (WeightedGraph);\
+//! Implementações para a lista de adjacência (Graph), o formato CSR (CsrGraph) e a matriz de adjacência (Vec<Vec<Option<W>>>).
+//!
+//! Dijkstra, Bellman Ford e Floyd Warshall só precisam do número de vértices, dos vizinhos (com os pesos) e do peso de uma aresta, então são escritos sobre esse trait e não sobre a estrutura concreta. Assim qualquer representação nova (um grid implícito, por exemplo) só precisa implementar "num_vertex" e "neighbors_with_weights".
+
+pub use crate::csr::CsrGraph;
+pub use crate::graph::{Graph, NeighborIter};
+pub use crate::weight::Weight;
+
+/// # Representa qualquer grafo com pesos do tipo W que os algoritmos conseguem percorrer.
+/// ## Métodos obrigatórios
+/// "num_vertex" - Quantidade de vértices do grafo;\
+/// "neighbors_with_weights" - Iterador com os pares (vizinho, peso) das arestas que saem de um vértice.
+/// ## Métodos com implementação padrão
+/// "vertices_list" - Lista dos vértices, de 0 até num_vertex - 1 (como no Graph::vertices_list());\
+/// "vizinhos" - Lista de vizinhos de um vértice;\
+/// "weight" - Peso da aresta entre dois vértices, se existir.
+
+pub trait WeightedGraph<W: Weight> {
+    fn num_vertex(&self) -> usize;
+
+    fn neighbors_with_weights(&self, vertice: usize) -> impl Iterator<Item = (usize, W)> + '_;
+
+    fn vertices_list(&self) -> Vec<usize> {
+        (0..self.num_vertex()).collect()
+    }
+
+    fn vizinhos(&self, vertice: &usize) -> Vec<usize> {
+        self.neighbors_with_weights(*vertice).map(|(v, _)| v).collect()
+    }
+
+    fn weight(&self, origem: &usize, destino: &usize) -> Option<W> {
+        self.neighbors_with_weights(*origem)
+            .find(|(v, _)| v == destino)
+            .map(|(_, peso)| peso)
+    }
+}
+
+impl<W: Weight> WeightedGraph<W> for Graph<W> {
+    fn num_vertex(&self) -> usize {
+        self.num_vertex
+    }
+
+    fn neighbors_with_weights(&self, vertice: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        Graph::neighbors_with_weights(self, vertice)
+    }
+}
+
+impl<W: Weight> WeightedGraph<W> for CsrGraph<W> {
+    fn num_vertex(&self) -> usize {
+        self.num_vertex
+    }
+
+    fn neighbors_with_weights(&self, vertice: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        let intervalo = self.intervalo(vertice);
+        self.targets[intervalo.clone()].iter().copied().zip(self.weights[intervalo].iter().copied())
+    }
+}
+
+/// # Matriz de adjacência: matriz[i][j] guarda o peso da aresta (i, j), ou None se ela não existir.
+
+impl<W: Weight> WeightedGraph<W> for Vec<Vec<Option<W>>> {
+    fn num_vertex(&self) -> usize {
+        self.len()
+    }
+
+    fn neighbors_with_weights(&self, vertice: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        self.get(vertice)
+            .into_iter()
+            .flat_map(|linha| linha.iter().enumerate().filter_map(|(j, peso)| peso.map(|p| (j, p))))
+    }
+}
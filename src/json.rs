@@ -0,0 +1,227 @@
+//! # Serialização em JSON (feature "serde")
+//! ## Conteúdo:
+//! Serialize e Deserialize para o Graph;\
+//! Resultados dos algoritmos prontos para serializar (ShortestPaths e AllPairs);\
+//! Mapa do "Cenário 3" com campos nomeados (GridMap);\
+//! Leitura e escrita de qualquer um deles em arquivo (read_json() e write_json()).
+//!
+//! Só é compilado com "cargo build --features serde". Os serviços trocam grafos e resultados em JSON em vez dos formatos de texto próprios:
+//! ```rust
+//!    let texto = serde_json::to_string(&gr)?;
+//!    let gr: Graph = serde_json::from_str(&texto)?;
+//!
+//!    let resultado = ShortestPaths::new(0, dijikstra(&gr, &0));
+//!    write_json(&resultado, "resultado.json")?;
+//! ```
+//! ## Formato do grafo
+//! A lista encadeada não é serializada como está (ela é recursiva e cada aresta não-direcionada aparece duas vezes); o grafo vira uma lista de arestas:
+//! ```rust
+//!    { "is_zero_based": false, "is_directed": true, "num_vertex": 3, "attribute_names": ["weight"], "parallel_edges": "KeepAll",
+//!      "labels": { "0": "Sé" }, "edges": [{ "origin": 0, "destination": 1, "attributes": [5] }] }
+//! ```
+//! Os vértices ficam 0-based, como no grafo. Pesos f32/f64 infinitos viram null no JSON e não podem ser lidos de volta.
+
+use std::collections::BTreeMap;
+
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub use crate::dijkstra::reconstruir_caminho;
+pub use crate::error::{GraphError, ParseErrorKind};
+pub use crate::graph::{Graph, Mapa, ParallelEdges, BufWriter, File, Write, fs};
+pub use crate::weight::Weight;
+
+/// # Aresta do grafo no JSON, com os atributos na ordem de "attribute_names".
+
+#[derive(Serialize, Deserialize)]
+struct EdgeJson<W> {
+    origin: usize,
+    destination: usize,
+    attributes: Vec<W>,
+}
+
+/// # Grafo no JSON, como lista de arestas (veja o formato na documentação do módulo).
+
+#[derive(Serialize, Deserialize)]
+struct GraphJson<W> {
+    is_zero_based: bool,
+    is_directed: bool,
+    num_vertex: usize,
+    attribute_names: Vec<String>,
+    #[serde(default)]
+    parallel_edges: ParallelEdges,
+    #[serde(default)]
+    labels: BTreeMap<usize, String>,
+    edges: Vec<EdgeJson<W>>,
+}
+
+impl<W: Weight + Serialize> Serialize for Graph<W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut edges = Vec::with_capacity(self.num_edges);
+        for (origem, node) in self.arestas_unicas() {
+            let attributes = (0..self.attribute_names.len()).map(|i| node.attribute(i).unwrap_or(W::zero())).collect();
+            edges.push(EdgeJson { origin: origem, destination: node.value, attributes });
+        }
+
+        let labels = self.labels.iter().flat_map(|labels| labels.iter()).map(|(v, nome)| (v, nome.to_string())).collect();
+
+        GraphJson {
+            is_zero_based: self.is_zero_based,
+            is_directed: self.is_directed,
+            num_vertex: self.num_vertex,
+            attribute_names: self.attribute_names.clone(),
+            parallel_edges: self.parallel_edges,
+            labels,
+            edges,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, W: Weight + Deserialize<'de>> Deserialize<'de> for Graph<W> {
+
+    /// Checa os vértices e a quantidade de atributos de cada aresta antes de montar o grafo.\
+    /// As arestas são incluídas todas (KeepAll) e a política "parallel_edges" só vale para as próximas inclusões.
+
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let json = GraphJson::<W>::deserialize(deserializer)?;
+        if json.attribute_names.is_empty() {
+            return Err(D::Error::custom("attribute_names precisa ter ao menos um nome"));
+        }
+
+        let nomes: Vec<&str> = json.attribute_names.iter().map(|nome| nome.as_str()).collect();
+        let mut grafo = Graph::with_attributes(json.is_zero_based, json.num_vertex, &nomes);
+        grafo.is_directed = json.is_directed;
+
+        for aresta in json.edges {
+            for vertice in [aresta.origin, aresta.destination] {
+                if vertice >= json.num_vertex {
+                    return Err(D::Error::custom(format!("vértice {} fora do intervalo 0..{}", vertice, json.num_vertex)));
+                }
+            }
+            if aresta.attributes.len() != nomes.len() {
+                return Err(D::Error::custom(format!("a aresta ({}, {}) tem {} atributos, mas eram esperados {}", aresta.origin, aresta.destination, aresta.attributes.len(), nomes.len())));
+            }
+            grafo.incluir_na_leitura(aresta.origin, aresta.destination, &aresta.attributes);
+        }
+        grafo.terminar_leitura();
+
+        for (vertice, nome) in json.labels {
+            if vertice >= json.num_vertex {
+                return Err(D::Error::custom(format!("vértice {} fora do intervalo 0..{}", vertice, json.num_vertex)));
+            }
+            grafo.set_label(vertice, &nome);
+        }
+
+        grafo.parallel_edges = json.parallel_edges;
+        Ok(grafo)
+    }
+}
+
+/// # Resultado do dijikstra() ou do bellman_ford() a partir de uma origem.
+/// ## Atributos
+/// "origin" - Vértice de origem (0-based);\
+/// "distances" - Custo até cada vértice;\
+/// "previous" - Vértice anterior no caminho mínimo até cada vértice.
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShortestPaths<W> {
+    pub origin: usize,
+    pub distances: Vec<W>,
+    pub previous: Vec<Option<usize>>,
+}
+
+impl<W> ShortestPaths<W> {
+
+    /// # Guarda o retorno do algoritmo: ShortestPaths::new(0, dijikstra(&gr, &0))
+
+    pub fn new(origin: usize, (distances, previous): (Vec<W>, Vec<Option<usize>>)) -> Self {
+        ShortestPaths { origin, distances, previous }
+    }
+
+    /// # Reconstrói o caminho da origem até o destino
+
+    pub fn path(&self, destino: usize) -> Vec<usize> {
+        reconstruir_caminho(self.origin, destino, &self.previous)
+    }
+}
+
+/// # Resultado do floyd_rot_n_cost(), com os caminhos mínimos entre todos os pares.
+/// ## Atributos
+/// "distances" - Matriz de custos;\
+/// "routes" - Matriz de rotas, routes[i][j] é o próximo vértice no caminho de i para j.
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllPairs<W> {
+    pub distances: Vec<Vec<W>>,
+    pub routes: Vec<Vec<Option<usize>>>,
+}
+
+impl<W> AllPairs<W> {
+
+    /// # Guarda o retorno do algoritmo: AllPairs::new(floyd_rot_n_cost(&gr))
+
+    pub fn new((distances, routes): (Vec<Vec<W>>, Vec<Vec<Option<usize>>>)) -> Self {
+        AllPairs { distances, routes }
+    }
+
+    /// # Reconstrói o caminho entre dois vértices
+
+    pub fn path(&self, origem: usize, destino: usize) -> Vec<usize> {
+        crate::floydwarshall::reconstruir_caminho(&self.routes, origem, destino)
+    }
+}
+
+/// # Mapa do "Cenário 3" com campos nomeados, igual ao retorno do read_map().
+/// ## Atributos
+/// "matrix" - A matriz de char do mapa;\
+/// "start" - Coordenadas (x, y) de 'S';\
+/// "goal" - Coordenadas (x, y) de 'G', com x a coluna e y a linha (matrix[y][x]).
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GridMap {
+    pub matrix: Vec<Vec<char>>,
+    pub start: (usize, usize),
+    pub goal: (usize, usize),
+}
+
+impl From<Mapa> for GridMap {
+    fn from((matrix, start, goal): Mapa) -> Self {
+        GridMap { matrix, start, goal }
+    }
+}
+
+impl From<GridMap> for Mapa {
+    fn from(mapa: GridMap) -> Self {
+        (mapa.matrix, mapa.start, mapa.goal)
+    }
+}
+
+/// # Função que salva qualquer valor serializável em um arquivo JSON (formatado).
+/// ## Retorno
+///
+/// Nada, ou um GraphError::Io com o caminho do arquivo.
+
+pub fn write_json<T: Serialize>(valor: &T, path: &str) -> Result<(), GraphError> {
+    let file = File::create(path).map_err(|e| GraphError::io(path, e))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer_pretty(&mut writer, valor).map_err(|e| GraphError::io(path, e.into()))?;
+    writer.flush().map_err(|e| GraphError::io(path, e))
+}
+
+/// # Função que lê qualquer valor desserializável de um arquivo JSON.
+/// ## Retorno
+///
+/// O valor lido, ou o GraphError com a linha e a coluna do problema (campo faltando, tipo errado, vértice fora do intervalo...).
+
+pub fn read_json<T: DeserializeOwned>(path: &str) -> Result<T, GraphError> {
+    let conteudo = fs::read_to_string(path).map_err(|e| GraphError::io(path, e))?;
+    serde_json::from_str(&conteudo).map_err(|e| {
+        // A mensagem do serde_json já termina com " at line L column C"
+        let mensagem = e.to_string();
+        let mensagem = mensagem.split(" at line ").next().unwrap_or_default().to_string();
+        // Erros da validação do grafo (depois de ler o JSON todo) vêm sem posição
+        let (linha, coluna) = if e.line() == 0 { (1, 1) } else { (e.line(), e.column()) };
+        GraphError::parse(path, linha, coluna, ParseErrorKind::Json(mensagem))
+    })
+}
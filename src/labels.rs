@@ -0,0 +1,108 @@
+//! # Nomes dos vértices
+//! ## Conteúdo:
+//! Tabela bidirecional nome <-> vértice (Labels).
+//!
+//! Os arquivos do metrô numeram as estações, mas os operadores pensam nos nomes. A tabela é opcional no Graph e, quando existe, é usada para mostrar o grafo, a estação central e os caminhos.
+
+pub use std::collections::{BTreeMap, HashMap};
+
+/// # Tabela bidirecional entre o vértice (0-based, como no grafo) e o seu nome.
+/// ## Atributos
+/// "nomes" - Nome de cada vértice que tem nome;\
+/// "ids" - Vértice de cada nome.
+///
+/// As duas direções são mantidas juntas: dar um nome novo a um vértice apaga o nome antigo, e reutilizar um nome tira ele do vértice anterior.
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(into = "BTreeMap<usize, String>", from = "BTreeMap<usize, String>"))]
+pub struct Labels {
+    nomes: HashMap<usize, String>,
+    ids: HashMap<String, usize>,
+}
+
+impl Labels {
+
+    /// # Cria uma tabela vazia
+
+    pub fn new() -> Self {
+        Labels::default()
+    }
+
+    /// # Dá um nome ao vértice
+
+    pub fn insert(&mut self, vertice: usize, nome: &str) {
+        if let Some(antigo) = self.nomes.remove(&vertice) {
+            self.ids.remove(&antigo);
+        }
+        if let Some(outro) = self.ids.remove(nome) {
+            self.nomes.remove(&outro);
+        }
+        self.nomes.insert(vertice, nome.to_string());
+        self.ids.insert(nome.to_string(), vertice);
+    }
+
+    /// # Retorna o nome do vértice, se existir
+
+    pub fn name(&self, vertice: usize) -> Option<&str> {
+        self.nomes.get(&vertice).map(|nome| nome.as_str())
+    }
+
+    /// # Retorna o vértice com esse nome, se existir
+
+    pub fn id(&self, nome: &str) -> Option<usize> {
+        self.ids.get(nome).copied()
+    }
+
+    /// # Quantidade de vértices com nome
+
+    pub fn len(&self) -> usize {
+        self.nomes.len()
+    }
+
+    /// # Retorna se a tabela está vazia
+
+    pub fn is_empty(&self) -> bool {
+        self.nomes.is_empty()
+    }
+
+    /// # Retorna os pares (vértice, nome) ordenados pelo vértice
+
+    pub fn iter(&self) -> Vec<(usize, &str)> {
+        let mut pares: Vec<(usize, &str)> = self.nomes.iter().map(|(v, nome)| (*v, nome.as_str())).collect();
+        pares.sort();
+        pares
+    }
+
+    /// # Remove o nome de um vértice removido do grafo e renumera os seguintes, igual ao Graph::remove_vertex()
+
+    pub fn remove_vertex(&mut self, vertice: usize) {
+        if let Some(nome) = self.nomes.remove(&vertice) {
+            self.ids.remove(&nome);
+        }
+        let pares: Vec<(usize, String)> = self.nomes.drain().collect();
+        self.ids.clear();
+        for (v, nome) in pares {
+            let novo = if v > vertice { v - 1 } else { v };
+            self.ids.insert(nome.clone(), novo);
+            self.nomes.insert(novo, nome);
+        }
+    }
+}
+
+/// # A tabela vira um mapa vértice -> nome (é assim que ela é serializada)
+
+impl From<Labels> for BTreeMap<usize, String> {
+    fn from(labels: Labels) -> Self {
+        labels.nomes.into_iter().collect()
+    }
+}
+
+impl From<BTreeMap<usize, String>> for Labels {
+    fn from(nomes: BTreeMap<usize, String>) -> Self {
+        let mut labels = Labels::new();
+        for (vertice, nome) in nomes {
+            labels.insert(vertice, &nome);
+        }
+        labels
+    }
+}
@@ -1,7 +1,26 @@
 //! # Descrição
 //! Nesta atividade, vamos explorar o conceito de caminho mínimo utilizando algoritmos como Djkistra, Bellman-Ford, Floyd. A atividade pode ser feita individual, dupla ou trio. Vamos explorar três cenários de aplicação, duas em que um grafo já oferecido como entrada e outra em que você precisa montar um grafo a partir de um mapa/grid.
 
+#![allow(clippy::empty_line_after_doc_comments)] // a documentação do projeto sempre deixa uma linha entre o comentário e o item
+
+pub mod weight;
+pub mod error;
+pub mod labels;
 pub mod graph;
+pub mod dimacs;
+pub mod csv;
+pub mod dot;
+pub mod xml;
+#[cfg(feature = "serde")]
+pub mod json;
+pub mod csr;
+pub mod snapshot;
+pub mod graph_trait;
+pub mod builder;
+pub mod operations;
+pub mod generators;
+pub mod stats;
+pub mod cost;
 pub mod dijkstra;
 pub mod bellman_ford;
 pub mod floydwarshall;
@@ -9,7 +28,7 @@ pub mod scenario_one;
 pub mod scenario_two;
 pub mod scenario_three;
 
-use std::{io::{self, Write}, thread, time};
+use std::io::{self, Write};
 pub use clearscreen;
 
 /// # Esse módulo traz consigo o primeiro cenário usando o algoritmo Floyd Warshall;
@@ -54,10 +73,7 @@ pub fn main() {
         io::stdout().flush().unwrap();
         let mut continue_input = String::new();
         io::stdin().read_line(&mut continue_input).expect("Falha ao ler a linha.");
-        let choose: i32 = match continue_input.trim().parse() {
-            Ok(n) => n,
-            Err(_) => 1, // Se a entrada for inválida, assume que quer continuar
-        };
+        let choose: i32 = continue_input.trim().parse().unwrap_or(1); // Se a entrada for inválida, assume que quer continuar
 
         match choose {
             0 => break,
@@ -0,0 +1,225 @@
+//! # Operações que criam um grafo novo a partir de outros
+//! ## Conteúdo:
+//! Grafo reverso (transpose());\
+//! Subgrafo induzido por um conjunto de vértices (induced_subgraph());\
+//! Subgrafo com as arestas que passam em um filtro (edge_subgraph());\
+//! União de duas redes (union()).
+//!
+//! Nenhuma operação altera o grafo original. O grafo novo mantém a numeração dos vértices, os nomes, a indexação (is_zero_based), os atributos das arestas e a política de arestas paralelas, e num_edges é recontado pelas próprias inclusões. Por exemplo, para uma busca de trás para frente até o destino:
+//! ```rust
+//!    let reverso = gr.transpose();
+//!    let (distancias_ate_destino, _) = dijikstra(&reverso, &destino);
+//!    let baratas = gr.edge_subgraph(|_, node| node.weight < 20);
+//! ```
+
+pub use crate::error::GraphError;
+pub use crate::graph::{Graph, Node, ParallelEdges};
+pub use crate::weight::Weight;
+
+impl<W: Weight> Graph<W> {
+
+    /// # Retorna o grafo com todas as arestas invertidas: (origem, destino) vira (destino, origem)
+    /// Em grafos não-direcionados é uma cópia do grafo.
+
+    pub fn transpose(&self) -> Graph<W> {
+        let mut grafo = self.vazio_como(self.num_vertex);
+        for (origem, node) in self.arestas_unicas() {
+            grafo.incluir_copia(node.value, origem, node);
+        }
+        grafo.parallel_edges = self.parallel_edges;
+        grafo
+    }
+
+    /// # Retorna o subgrafo induzido pelos vértices: só as arestas com as duas pontas no conjunto
+    /// ## Argumentos
+    /// "vertices" - Os vértices (0-based) mantidos. Vértices repetidos ou fora do grafo são ignorados.
+    /// ## Numeração
+    /// Os vértices não são renumerados, então num_vertex e os nomes continuam os mesmos e um caminho no subgrafo vale no grafo original. Os vértices fora do conjunto só ficam sem arestas.
+
+    pub fn induced_subgraph(&self, vertices: &[usize]) -> Graph<W> {
+        let mut mantidos = vec![false; self.num_vertex];
+        for &vertice in vertices {
+            if let Some(mantido) = mantidos.get_mut(vertice) {
+                *mantido = true;
+            }
+        }
+        let dentro = |vertice: usize| mantidos.get(vertice).copied().unwrap_or(false);
+        self.edge_subgraph(|origem, node| dentro(origem) && dentro(node.value))
+    }
+
+    /// # Retorna o subgrafo só com as arestas que passam no filtro
+    /// ## Argumentos
+    /// "manter" - Recebe a origem e o nó da aresta (destino em "value", peso em "weight" e os demais atributos em attribute()) e retorna se ela fica.
+    ///
+    /// Em grafos não-direcionados o filtro é chamado uma vez para cada aresta, com origem <= destino. Os vértices e os nomes são mantidos, como no induced_subgraph().
+    /// ```rust
+    ///    let baratas = gr.edge_subgraph(|_, node| node.weight < 20);
+    ///    let curtas = gr.edge_subgraph(|_, node| node.attribute(gr.attribute_index("time").unwrap()) < Some(5));
+    /// ```
+
+    pub fn edge_subgraph<F: FnMut(usize, &Node<W>) -> bool>(&self, mut manter: F) -> Graph<W> {
+        let mut grafo = self.vazio_como(self.num_vertex);
+        for (origem, node) in self.arestas_unicas() {
+            if manter(origem, node) {
+                grafo.incluir_copia(origem, node.value, node);
+            }
+        }
+        grafo.parallel_edges = self.parallel_edges;
+        grafo
+    }
+
+    /// # Retorna a união das duas redes: os vértices e as arestas dos dois grafos
+    /// ## Argumentos
+    /// "outro" - O grafo unido a este, com a mesma direção e os mesmos atributos das arestas.
+    /// ## Retorno
+    /// O grafo com num_vertex igual ao maior dos dois, as arestas deste grafo e depois as do outro, e os nomes dos dois. A indexação e a política de arestas paralelas são as deste grafo.
+    /// ## Arestas nos dois grafos
+    /// A política de arestas paralelas deste grafo decide o que fazer com uma aresta do outro que já existe: KeepAll guarda as duas, KeepMin e KeepLast guardam uma e Reject gera o erro DuplicateEdge.
+    /// ## Erros
+    /// IncompatibleGraphs se a direção ou os atributos forem diferentes, ou se o mesmo vértice tiver nomes diferentes nos dois grafos;\
+    /// DuplicateLabel se o mesmo nome estiver em vértices diferentes.
+
+    pub fn union(&self, outro: &Graph<W>) -> Result<Graph<W>, GraphError> {
+        if self.is_directed != outro.is_directed {
+            return Err(GraphError::IncompatibleGraphs("um grafo é direcionado e o outro não".to_string()));
+        }
+        if self.attribute_names != outro.attribute_names {
+            return Err(GraphError::IncompatibleGraphs(format!("os atributos das arestas são diferentes ({} e {})", self.attribute_names.join(", "), outro.attribute_names.join(", "))));
+        }
+
+        let mut grafo = self.vazio_como(self.num_vertex.max(outro.num_vertex));
+        for (origem, node) in self.arestas_unicas() {
+            grafo.incluir_copia(origem, node.value, node);
+        }
+        grafo.parallel_edges = self.parallel_edges;
+        for (origem, node) in outro.arestas_unicas() {
+            grafo.edge_with_attributes(origem, node.value, &atributos(node))?;
+        }
+
+        for (vertice, nome) in outro.labels.iter().flat_map(|labels| labels.iter()) {
+            match grafo.label(vertice) {
+                Some(atual) if atual != nome => {
+                    return Err(GraphError::IncompatibleGraphs(format!("o vértice {} se chama \"{}\" em um grafo e \"{}\" no outro", vertice, atual, nome)));
+                }
+                Some(_) => {}
+                None if grafo.vertex_by_label(nome).is_some() => return Err(GraphError::DuplicateLabel(nome.to_string())),
+                None => grafo.set_label(vertice, nome),
+            }
+        }
+
+        Ok(grafo)
+    }
+
+    /// # Grafo sem arestas com as mesmas configurações e os mesmos nomes (a política de arestas paralelas é copiada depois das arestas)
+
+    fn vazio_como(&self, num_vertex: usize) -> Graph<W> {
+        let nomes: Vec<&str> = self.attribute_names.iter().map(|nome| nome.as_str()).collect();
+        let mut grafo = Graph::with_attributes(self.is_zero_based, num_vertex, &nomes);
+        grafo.is_directed = self.is_directed;
+        grafo.labels = self.labels.clone();
+        grafo
+    }
+
+    /// # Inclui uma cópia da aresta com todos os atributos, sem passar pela política de arestas paralelas
+
+    fn incluir_copia(&mut self, origem: usize, destino: usize, node: &Node<W>) {
+        self.incluir_aresta(origem, destino, &atributos(node));
+    }
+}
+
+/// # Todos os atributos da aresta, na ordem de attribute_names (o peso primeiro)
+
+fn atributos<W: Weight>(node: &Node<W>) -> Vec<W> {
+    std::iter::once(node.weight).chain(node.extra.iter().copied()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::tests::arestas_com_atributos;
+
+    /// # Grafo direcionado 0-based com dois atributos, um nome e duas arestas paralelas (0, 1)
+
+    fn grafo_de_exemplo() -> Graph {
+        let mut gr: Graph = Graph::with_attributes(true, 4, &["distance", "time"]);
+        gr.edge_with_attributes(0, 1, &[5, 3]).unwrap();
+        gr.edge_with_attributes(0, 1, &[2, 9]).unwrap();
+        gr.edge_with_attributes(1, 2, &[-1, 4]).unwrap();
+        gr.edge_with_attributes(3, 0, &[7, 1]).unwrap();
+        gr.set_label(2, "Centro");
+        gr
+    }
+
+    #[test]
+    fn transpose_inverte_as_arestas_e_mantem_a_contagem() {
+        let gr = grafo_de_exemplo();
+        let reverso = gr.transpose();
+
+        assert_eq!((reverso.num_vertex, reverso.num_edges), (4, 4));
+        let mut esperado: Vec<_> = arestas_com_atributos(&gr).into_iter().map(|(origem, destino, atributos)| (destino, origem, atributos)).collect();
+        let mut invertido = arestas_com_atributos(&reverso);
+        esperado.sort();
+        invertido.sort();
+        assert_eq!(invertido, esperado);
+        assert_eq!(reverso.label(2), Some("Centro"));
+        assert_eq!(arestas_com_atributos(&reverso.transpose()).len(), 4);
+    }
+
+    #[test]
+    fn subgrafos_contam_so_as_arestas_mantidas() {
+        let gr = grafo_de_exemplo();
+
+        let induzido = gr.induced_subgraph(&[0, 1, 1, 99]);
+        assert_eq!((induzido.num_vertex, induzido.num_edges), (4, 2));
+        assert!(induzido.edges().all(|(origem, destino, _)| origem <= 1 && destino <= 1));
+
+        let baratas = gr.edge_subgraph(|_, node| node.weight < 5);
+        let mut arestas = arestas_com_atributos(&baratas);
+        arestas.sort();
+        assert_eq!(baratas.num_edges, 2);
+        assert_eq!(arestas, vec![(0, 1, vec![2, 9]), (1, 2, vec![-1, 4])]);
+        assert_eq!(gr.num_edges, 4);
+    }
+
+    #[test]
+    fn union_soma_as_arestas_seguindo_a_politica() {
+        let gr = grafo_de_exemplo();
+        let mut outro: Graph = Graph::with_attributes(true, 6, &["distance", "time"]);
+        outro.edge_with_attributes(1, 2, &[8, 8]).unwrap();
+        outro.edge_with_attributes(4, 5, &[1, 2]).unwrap();
+        outro.set_label(5, "Norte");
+
+        let uniao = gr.union(&outro).unwrap();
+        assert_eq!((uniao.num_vertex, uniao.num_edges), (6, 6));
+        assert_eq!((uniao.label(2), uniao.label(5)), (Some("Centro"), Some("Norte")));
+
+        let mut gr = gr;
+        gr.parallel_edges = ParallelEdges::KeepMin;
+        let uniao = gr.union(&outro).unwrap();
+        assert_eq!(uniao.num_edges, 5);
+        assert_eq!(uniao.weight(&1, &2), Some(-1));
+
+        gr.parallel_edges = ParallelEdges::Reject;
+        assert!(matches!(gr.union(&outro), Err(GraphError::DuplicateEdge { origin: 1, destination: 2 })));
+    }
+
+    #[test]
+    fn union_recusa_grafos_incompativeis() {
+        let gr = grafo_de_exemplo();
+
+        let mut nao_direcionado: Graph = Graph::with_attributes(true, 4, &["distance", "time"]);
+        nao_direcionado.is_directed = false;
+        assert!(matches!(gr.union(&nao_direcionado), Err(GraphError::IncompatibleGraphs(_))));
+
+        let outros_atributos: Graph = Graph::with_attributes(true, 4, &["distance"]);
+        assert!(matches!(gr.union(&outros_atributos), Err(GraphError::IncompatibleGraphs(_))));
+
+        let mut outro_nome: Graph = Graph::with_attributes(true, 4, &["distance", "time"]);
+        outro_nome.set_label(2, "Sul");
+        assert!(matches!(gr.union(&outro_nome), Err(GraphError::IncompatibleGraphs(_))));
+
+        let mut nome_repetido: Graph = Graph::with_attributes(true, 4, &["distance", "time"]);
+        nome_repetido.set_label(3, "Centro");
+        assert!(matches!(gr.union(&nome_repetido), Err(GraphError::DuplicateLabel(nome)) if nome == "Centro"));
+    }
+}
@@ -2,7 +2,7 @@
 //! ## Descrição
 //! Considere um grafo não-direcionado com pesos que representam pontos e conexões de metrô. Precisamos definir qual seria a estação central, ou o vértice central do grafo. O vértice central é o que consegue chegar a qualquer um dos outros vértices com o menor custo. Isso leva em conta tanto o somatório das distâncias do vértice em questão a cada um dos demais vértices.
 //! ## Output esperado
-//! O nó que representa a estação central escolhida;\
+//! O nó que representa a estação central escolhida (pelo nome, se o arquivo declarar nomes);\
 //! Um vetor com as distâncias da estação central até os demais vértices;\
 //! O vértice mais distante da estação central, junto com o valor de distância;\
 //! Uma matriz em que cada linha representa um vértice candidato à estação central e cada coluna é a distância mínima entre o vértice candidato e o vértice representante da coluna.
@@ -11,23 +11,23 @@
 //! ### Motivação
 //! Apesar de não ser o mais eficiênte, o principal motivo foi comodidade, o algoritmo já faz o retorno de todos os outputs esperados no cenário.
 
-pub use crate::graph::read_graph;
+pub use crate::graph::{read_graph_with, Graph, Indexing, ReadOptions};
 pub use crate::floydwarshall::floyd_rot_n_cost;
 
 /// # Define a estação central
 /// A estação estral é aquela em que a soma dos pesos dos caminhos a partir dela é a menor de todos os outros vértices.
-pub fn def_central_station(matrix : &Vec<Vec<i32>>, num_v : usize) -> usize{
+pub fn def_central_station(matrix : &[Vec<i32>], num_v : usize) -> usize{
     let inf = i32::MAX/2;
     let mut lowest_sum = i32::MAX;
     let mut c_station = 0;
-    for i in 0..num_v{
+    for (i, row) in matrix.iter().enumerate().take(num_v){
         let mut sum = 0;
-        for j in 0..num_v{
-            if matrix[i][j] == inf{
+        for &distance in row.iter().take(num_v){
+            if distance == inf{
                 sum = i32::MAX;
                 break;
             }
-            sum = sum + matrix[i][j]; 
+            sum += distance; 
         }
         if sum < lowest_sum{
             lowest_sum = sum;
@@ -45,8 +45,7 @@ pub fn station_vector(matrix: &[Vec<i32>], station: usize, num_v: usize) -> (Vec
     let mut farthest_station = 0;
     let mut farthest_distance = i32::MIN;
     
-    for i in 0..num_v {
-        let distancia_atual = matrix[station - 1][i];
+    for (i, &distancia_atual) in matrix[station - 1].iter().enumerate().take(num_v) {
         ev.push(distancia_atual); 
         if distancia_atual > farthest_distance {
             farthest_distance = distancia_atual;
@@ -58,61 +57,62 @@ pub fn station_vector(matrix: &[Vec<i32>], station: usize, num_v: usize) -> (Vec
 }
 
 /// # Printa a matriz de distandias de todas as estações para todas estações.
-pub fn print_matrix(matrix : &Vec<Vec<i32>>, num_v : usize){
+/// Cada linha começa com o nome da estação (vertex_name()), então grafos lidos com ids esparsos mostram os ids originais.
+pub fn print_matrix(gr : &Graph, matrix : &[Vec<i32>], num_v : usize){
     let inf = i32::MAX/2;
-    for i in 0..num_v{
-        print!("{} -> ", i + 1);
-        for j in 0..num_v{
-            if matrix[i][j] != inf{
-                print!("{} ", matrix[i][j]);
+    for (i, row) in matrix.iter().enumerate().take(num_v){
+        print!("{} -> ", gr.vertex_name(i));
+        for &distance in row.iter().take(num_v){
+            if distance != inf{
+                print!("{} ", distance);
             }
             else{
                 print!("# ");
             }
         }
-        print!("\n");
+        println!();
     }
 }
 
 /// # Impreime um vetor.
-pub fn print_vector(vector : &Vec<i32>){
+pub fn print_vector(vector : &[i32]){
     print!("[");
-    for i in 0..vector.len(){
-        print!("{}", vector[i]);
+    for (i, value) in vector.iter().enumerate(){
+        print!("{}", value);
         if i < vector.len()-1{
             print!(", ");
         }
     }
-    print!("]\n");
+    println!("]");
 }
 
 /// # Função de chamada do primeiro cenário
 /// ## Mudando o grafo
 /// Para mudar o grafo lido basta alterar o caminho presente na seguinte função:
 /// ```rust
-///    let gr = match read_graph("data/graph1.txt")
+///    let gr = match read_graph_with("data/graph1.txt", &ReadOptions { indexing: Indexing::OneBased, ..Default::default() })
 /// ```
 /// ## Chamada do Floyd Wharshall
 /// ```rust
-///    let (matrix, matrix_rot) = floyd_rot_n_cost(&gr);
+///    let (matrix, _matrix_rot) = floyd_rot_n_cost(&gr);
 /// ```
 /// ## Outputs
 /// ```rust
 ///    let central_station = def_central_station(&matrix, num_v);
 ///    let (central_station_vector, farthest_station, farthest_distance) = station_vector(&matrix, central_station, num_v);
-///    print!("The node that represents the choosen central station: {}\n", central_station);
+///    print!("The node that represents the choosen central station: {}\n", gr.vertex_name(central_station - 1));
 ///    print!("\n");
 ///    print!("A vector with the distances from the central station to the other vertices:");
 ///    print_vector(&central_station_vector);
 ///    print!("\n");
-///    print!("The vertex furthest from the central station, along with the distance value: {}, d = {}\n", farthest_station, farthest_distance);
+///    print!("The vertex furthest from the central station, along with the distance value: {}, d = {}\n", gr.vertex_name(farthest_station - 1), farthest_distance);
 ///    print!("\n");
 ///    print!("Matrix in which each row represents a candidate vertex for the central station and each column is the minimum distance between the candidate vertex and the column's representative vertex:\n");
-///    print_matrix(&matrix, num_v);
+///    print_matrix(&gr, &matrix, num_v);
 ///    print!("\n");
 /// ```
 pub fn first_scenario(){
-    let gr = match read_graph("data/graph1.txt") {
+    let gr = match read_graph_with("data/graph1.txt", &ReadOptions { indexing: Indexing::OneBased, ..Default::default() }) {
         Ok(graph_sucesso) => {
             graph_sucesso.print();
             println!("Graph successfully read from file!\n");
@@ -124,21 +124,21 @@ pub fn first_scenario(){
         }
     };
 
-    let (matrix, matrix_rot) = floyd_rot_n_cost(&gr);
+    let (matrix, _matrix_rot) = floyd_rot_n_cost(&gr);
     let num_v = gr.num_vertex();
     let central_station = def_central_station(&matrix, num_v);
     let (central_station_vector, farthest_station, farthest_distance) = station_vector(&matrix, central_station, num_v);
 
 
-    print!("The node that represents the choosen central station: {}\n", central_station);
-    print!("\n");
+    println!("The node that represents the choosen central station: {}", gr.vertex_name(central_station - 1));
+    println!();
     print!("A vector with the distances from the central station to the other vertices:");
     print_vector(&central_station_vector);
-    print!("\n");
-    print!("The vertex furthest from the central station, along with the distance value: {}, d = {}\n", farthest_station, farthest_distance);
-    print!("\n");
-    print!("Matrix in which each row represents a candidate vertex for the central station and each column is the minimum distance between the candidate vertex and the column's representative vertex:\n");
-    print_matrix(&matrix, num_v);
-    print!("\n");
+    println!();
+    println!("The vertex furthest from the central station, along with the distance value: {}, d = {}", gr.vertex_name(farthest_station - 1), farthest_distance);
+    println!();
+    println!("Matrix in which each row represents a candidate vertex for the central station and each column is the minimum distance between the candidate vertex and the column's representative vertex:");
+    print_matrix(&gr, &matrix, num_v);
+    println!();
 
 }
\ No newline at end of file
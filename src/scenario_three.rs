@@ -10,9 +10,10 @@
 
 pub use crate::graph::read_map;
 pub use crate::graph::map_to_txt;
-pub use crate::graph::read_graph;
+pub use crate::graph::{read_graph_with, Indexing, ReadOptions};
 pub use crate::dijkstra::dijikstra;
 pub use crate::dijkstra::reconstruir_caminho;
+pub use crate::graph::Graph;
 
 /// # Transforma um par de coordenadas da matriz no n° do nó especifico do grafo
 /// ```rust
@@ -21,13 +22,11 @@ pub use crate::dijkstra::reconstruir_caminho;
 pub fn achar_node(coords: &(usize, usize), coluumns: usize) -> usize {
     let x = coords.0;
     let y = coords.1;
-    let v = x + (y * coluumns);
-
-    v
+    x + (y * coluumns)
 }
 
 /// # Transforma um vetor de caminho em um vetor de string com direções
-pub fn caminho_coord(caminho: &Vec<usize>, col: usize) -> Vec<&str>{
+pub fn caminho_coord(caminho: &[usize], col: usize) -> Vec<&str>{
     let col_isize = col as isize; // Converter 'col' para isize para comparações
     let col_neg_isize = -col_isize; // Agora este é o valor negativo correto
 
@@ -54,7 +53,7 @@ pub fn caminho_coord(caminho: &Vec<usize>, col: usize) -> Vec<&str>{
 /// ## Mudando o grafo
 /// Para mudar o grafo lido basta alterar o caminho presente na seguinte função:
 /// ```rust
-///    if let Ok((matrix, start, goal)) = read_map("data/grid_example.txt")
+///    match read_map("data/grid_example.txt")
 /// ```
 /// ## Chamada do Dijkstra
 /// ```rust
@@ -74,24 +73,26 @@ pub fn third_scenario(){
     let mut g_node = 0;
     let mut cols = 0;
 
-    if let Ok((matrix, start, goal)) = read_map("data/grid_example.txt") {
-        println!("Map read successfully!");
-        println!("Start: {:?}, Goal: {:?}", start, goal);
-        
-        cols = matrix[0].len();
-        s_node = achar_node(&start, cols);
-        g_node = achar_node(&goal, cols);
-        //println!("{} {}", s_node, g_node);
+    match read_map("data/grid_example.txt") {
+        Ok((matrix, start, goal)) => {
+            println!("Map read successfully!");
+            println!("Start: {:?}, Goal: {:?}", start, goal);
 
-        if let Err(e) = map_to_txt(&matrix) {
-            eprintln!("Error writing graph file: {}", e);
-        }
+            cols = matrix[0].len();
+            s_node = achar_node(&start, cols);
+            g_node = achar_node(&goal, cols);
+            //println!("{} {}", s_node, g_node);
 
-    } else {
-        eprintln!("Failed to read map from file.");
+            if let Err(e) = map_to_txt(&matrix) {
+                eprintln!("Error writing graph file: {}", e);
+            }
+        },
+        Err(e) => {
+            eprintln!("Failed to read map from file: {}", e);
+        }
     }
 
-    let gr = match read_graph("data/graph3.txt") {
+    let gr: Graph = match read_graph_with("data/graph3.txt", &ReadOptions { indexing: Indexing::OneBased, ..Default::default() }) {
         Ok(graph_sucesso) => {
             println!("Graph successfully read from file!\n");
             graph_sucesso // Se der certo, `gr` recebe o valor do grafo
@@ -13,7 +13,7 @@
 pub use std::io;
 pub use std::fs;
 
-pub use crate::graph::read_graph;
+pub use crate::graph::{read_graph_with, Indexing, ReadOptions};
 pub use crate::bellman_ford::bellman_ford;
 pub use crate::dijkstra::reconstruir_caminho;
 pub use crate::graph::Graph;
@@ -22,7 +22,7 @@ pub use crate::graph::Graph;
 /// ## Mudando o grafo
 /// Para mudar o grafo lido basta alterar o caminho presente na seguinte função:
 /// ```rust
-///    let gr = match read_graph("data/graph2.txt")
+///    let gr = match read_graph_with("data/graph2.txt", &ReadOptions { indexing: Indexing::ZeroBased, ..Default::default() })
 /// ```
 /// ## Chamada do Bellman Ford
 /// ```rust
@@ -31,11 +31,11 @@ pub use crate::graph::Graph;
 /// ## Outputs
 /// ```rust
 ///    let caminho = reconstruir_caminho(0, 6, &anteriores);
-///    println!("The path from vertex {} to {} is: {:?}", 0, 6, caminho);
+///    println!("The path from vertex {} to {} is: {}", gr.vertex_name(0), gr.vertex_name(6), gr.format_path(&caminho));
 //     println!("The total cost of the trip is: {:?}", distancias[6]);
 /// ```
 pub fn second_scenario(){
-    let gr = match read_graph("data/graph2.txt") {
+    let gr: Graph = match read_graph_with("data/graph2.txt", &ReadOptions { indexing: Indexing::ZeroBased, ..Default::default() }) {
         Ok(graph_sucesso) => {
             graph_sucesso.print();
             println!("Graph successfully read from file!\n");
@@ -50,6 +50,6 @@ pub fn second_scenario(){
     let (distancias, anteriores) = bellman_ford(&gr, &0);
     let caminho = reconstruir_caminho(0, 6, &anteriores);
 
-    println!("The path from vertex {} to {} is: {:?}", 0, 6, caminho);
+    println!("The path from vertex {} to {} is: {}", gr.vertex_name(0), gr.vertex_name(6), gr.format_path(&caminho));
     println!("The total cost of the trip is: {:?}", distancias[6]);
 }
\ No newline at end of file
@@ -0,0 +1,575 @@
+//! # Snapshot binário do grafo, aberto com memory-map
+//! ## Conteúdo:
+//! Pesos que podem ser gravados em binário (SnapshotWeight);\
+//! Escrita do snapshot (write_snapshot() e write_snapshot_to());\
+//! Snapshot aberto direto do disco (Snapshot), que os algoritmos já conseguem percorrer.
+//!
+//! O read_graph() lê e interpreta o texto todo a cada execução, o que demora em grafos com dezenas de milhões de arestas. O snapshot guarda o grafo já no formato CSR (veja o csr.rs), então abrir é só mapear o arquivo na memória e checar o cabeçalho:
+//! ```rust
+//!    let gr: Graph = read_graph("data/rede.txt")?;
+//!    write_snapshot(&gr, "data/rede.snap")?;
+//!
+//!    // nas próximas execuções
+//!    let snap: Snapshot<i32> = Snapshot::open("data/rede.snap")?;
+//!    let (distancias, anteriores) = dijikstra(&snap, &0);
+//! ```
+//! ## Formato (versão 1, little-endian, seções alinhadas em 8 bytes)
+//! ```rust
+//!    0..8    "GIRSNAP\0"
+//!    8..12   versão (u32)
+//!    12      tipo do peso (SnapshotWeight::TAG)
+//!    13      flags: bit 0 = is_zero_based, bit 1 = is_directed
+//!    14      política de arestas paralelas (0 = KeepAll, 1 = KeepMin, 2 = KeepLast, 3 = Reject)
+//!    16..64  num_vertex, linhas do CSR, arcos guardados, num_edges, quantidade de atributos, tamanho dos metadados (u64)
+//!    64..72  checksum (u64) de tudo que vem depois do cabeçalho
+//!    72..    metadados (nomes dos atributos e dos vértices), offsets, targets e uma coluna para cada atributo
+//! ```
+
+use std::io::{self, Seek, SeekFrom};
+use std::marker::PhantomData;
+
+use memmap2::Mmap;
+
+pub use crate::csr::CsrGraph;
+pub use crate::error::GraphError;
+pub use crate::graph::{Graph, ParallelEdges, BufWriter, File, Write};
+pub use crate::graph_trait::WeightedGraph;
+pub use crate::labels::Labels;
+pub use crate::weight::Weight;
+
+const MAGICO: &[u8; 8] = b"GIRSNAP\0";
+const VERSAO: u32 = 1;
+const TAMANHO_CABECALHO: usize = 72;
+
+/// # Representa um peso que pode ser gravado no snapshot.
+/// ## Requisitos
+/// "TAG" - Identificador do tipo, gravado no cabeçalho para não abrir um snapshot de i32 como f64;\
+/// "SIZE" - Quantidade de bytes de cada peso;\
+/// "write_le" e "read_le" - Conversão para e de bytes little-endian.
+///
+/// Implementado para os inteiros e floats de tamanho fixo (isize e usize ficam de fora porque o tamanho muda com a plataforma).
+
+pub trait SnapshotWeight: Weight {
+    const TAG: u8;
+    const SIZE: usize;
+    fn write_le(self, saida: &mut Vec<u8>);
+    fn read_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_snapshot_weight {
+    ($($t:ty => $tag:expr),*) => {
+        $(
+            impl SnapshotWeight for $t {
+                const TAG: u8 = $tag;
+                const SIZE: usize = std::mem::size_of::<$t>();
+                fn write_le(self, saida: &mut Vec<u8>) { saida.extend_from_slice(&self.to_le_bytes()) }
+                fn read_le(bytes: &[u8]) -> Self { <$t>::from_le_bytes(bytes.try_into().expect("SIZE bytes")) }
+            }
+        )*
+    };
+}
+
+impl_snapshot_weight!(i8 => 1, i16 => 2, i32 => 3, i64 => 4, i128 => 5, u8 => 6, u16 => 7, u32 => 8, u64 => 9, u128 => 10, f32 => 11, f64 => 12);
+
+/// # Função que salva o grafo em um snapshot binário.
+/// ## Retorno
+///
+/// Nada, ou um GraphError::Io com o caminho do arquivo.
+
+pub fn write_snapshot<W: SnapshotWeight>(grafo: &Graph<W>, path: &str) -> Result<(), GraphError> {
+    let file = File::create(path).map_err(|e| GraphError::io(path, e))?;
+    let mut writer = BufWriter::new(file);
+    write_snapshot_to(grafo, &mut writer).and_then(|_| writer.flush()).map_err(|e| GraphError::io(path, e))
+}
+
+/// # Função que escreve o snapshot em qualquer destino que aceite voltar para o início (File, BufWriter<File>, Cursor<Vec<u8>>...).
+/// O corpo é escrito direto das listas de adjacência enquanto o checksum é calculado, sem montar uma cópia do grafo na memória. No final o escritor volta para o início e grava o cabeçalho com o checksum.
+/// ## Erros
+/// Um erro de io::ErrorKind::InvalidInput se algum vértice com nome estiver fora de num_vertex (o Snapshot::open() recusaria o arquivo).
+
+pub fn write_snapshot_to<W: SnapshotWeight, O: Write + Seek>(grafo: &Graph<W>, writer: &mut O) -> io::Result<()> {
+    let labels = grafo.labels.as_ref().map(|labels| labels.iter()).unwrap_or_default();
+    if let Some((vertice, nome)) = labels.iter().find(|(vertice, _)| *vertice >= grafo.num_vertex) {
+        let mensagem = format!("o vértice {} (\"{}\") está fora dos {} vértices do grafo", vertice, nome, grafo.num_vertex);
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, mensagem));
+    }
+
+    // Linhas do CSR: cobre tanto o num_vertex quanto qualquer vértice que aparecer nas arestas, como no CsrGraph::from_graph()
+    let listas = || grafo.adj.iter().map(|(origem, lista)| (*origem, std::iter::successors(lista.as_deref(), |node| node.next.as_deref())));
+    let mut linhas = grafo.num_vertex;
+    let mut arcos = 0;
+    for (origem, lista) in listas() {
+        linhas = linhas.max(origem + 1);
+        for node in lista {
+            linhas = linhas.max(node.value + 1);
+            arcos += 1;
+        }
+    }
+    let num_atributos = grafo.attribute_names.len();
+
+    // O cabeçalho só é conhecido no fim (checksum e tamanho dos metadados), então o lugar dele fica reservado
+    let inicio = writer.stream_position()?;
+    writer.write_all(&[0; TAMANHO_CABECALHO])?;
+    let mut corpo = CorpoComChecksum::new(writer);
+
+    // Metadados: nomes dos atributos e dos vértices
+    for nome in &grafo.attribute_names {
+        corpo.texto(nome)?;
+    }
+    corpo.escrever(&(labels.len() as u64).to_le_bytes())?;
+    for (vertice, nome) in labels {
+        corpo.escrever(&(vertice as u64).to_le_bytes())?;
+        corpo.texto(nome)?;
+    }
+    corpo.alinhar()?;
+    let tamanho_meta = corpo.tamanho;
+
+    // Offsets: soma acumulada do grau de saída de cada linha
+    let mut offset = 0;
+    let mut com_lista = listas().peekable();
+    for v in 0..=linhas {
+        corpo.escrever(&(offset as u64).to_le_bytes())?;
+        if let Some((_, lista)) = com_lista.next_if(|(origem, _)| *origem == v) {
+            offset += lista.count();
+        }
+    }
+    for (_, lista) in listas() {
+        for node in lista {
+            corpo.escrever(&(node.value as u64).to_le_bytes())?;
+        }
+    }
+
+    // Uma coluna por atributo, na mesma ordem dos targets
+    for atributo in 0..num_atributos {
+        for (_, lista) in listas() {
+            for node in lista {
+                corpo.peso(node.attribute(atributo).unwrap_or(W::zero()))?;
+            }
+        }
+        corpo.alinhar()?;
+    }
+    let (hash, tamanho_corpo) = corpo.terminar()?;
+
+    let politica = match grafo.parallel_edges {
+        ParallelEdges::KeepAll => 0,
+        ParallelEdges::KeepMin => 1,
+        ParallelEdges::KeepLast => 2,
+        ParallelEdges::Reject => 3,
+    };
+    let flags = u8::from(grafo.is_zero_based) | (u8::from(grafo.is_directed) << 1);
+
+    let mut cabecalho = Vec::with_capacity(TAMANHO_CABECALHO);
+    cabecalho.extend_from_slice(MAGICO);
+    cabecalho.extend_from_slice(&VERSAO.to_le_bytes());
+    cabecalho.extend_from_slice(&[W::TAG, flags, politica, 0]);
+    for valor in [grafo.num_vertex, linhas, arcos, grafo.num_edges, num_atributos, tamanho_meta] {
+        cabecalho.extend_from_slice(&(valor as u64).to_le_bytes());
+    }
+    cabecalho.extend_from_slice(&hash.to_le_bytes());
+
+    writer.seek(SeekFrom::Start(inicio))?;
+    writer.write_all(&cabecalho)?;
+    writer.seek(SeekFrom::Start(inicio + (TAMANHO_CABECALHO + tamanho_corpo) as u64))?;
+    Ok(())
+}
+
+/// # Snapshot aberto com memory-map: as arestas são lidas direto do arquivo, sem copiar.
+/// ## Atributos
+/// "is_zero_based", "is_directed", "num_vertex", "num_edges", "parallel_edges", "attribute_names" e "labels" - Os mesmos do grafo salvo;\
+/// Os demais guardam o arquivo mapeado e onde começa cada seção.
+
+#[derive(Debug)]
+pub struct Snapshot<W> {
+    pub is_zero_based: bool,
+    pub is_directed: bool,
+    pub num_vertex: usize,
+    pub num_edges: usize,
+    pub parallel_edges: ParallelEdges,
+    pub attribute_names: Vec<String>,
+    pub labels: Option<Labels>,
+    mmap: Mmap,
+    linhas: usize,
+    arcos: usize,
+    inicio_offsets: usize,
+    inicio_targets: usize,
+    inicio_atributos: usize,
+    _peso: PhantomData<W>,
+}
+
+impl<W: SnapshotWeight> Snapshot<W> {
+
+    /// # Abre o snapshot, conferindo o cabeçalho, o checksum e se todos os vértices estão no intervalo
+    /// ## Retorno
+    /// O snapshot, um GraphError::Io se o arquivo não abrir ou um GraphError::InvalidSnapshot dizendo o que está errado.
+
+    pub fn open(path: &str) -> Result<Self, GraphError> {
+        Snapshot::abrir(path, true)
+    }
+
+    /// # Abre o snapshot conferindo só o cabeçalho e os offsets
+    /// Pula o checksum e a checagem dos targets (que leem o arquivo inteiro), para arquivos grandes em que o tempo de abertura importa. Um arquivo corrompido pode causar pânico nos algoritmos.
+
+    pub fn open_unverified(path: &str) -> Result<Self, GraphError> {
+        Snapshot::abrir(path, false)
+    }
+
+    fn abrir(path: &str, verificar: bool) -> Result<Self, GraphError> {
+        let invalido = |reason: String| GraphError::InvalidSnapshot { file: path.to_string(), reason };
+
+        let file = File::open(path).map_err(|e| GraphError::io(path, e))?;
+        // SAFETY: o mapa é só de leitura; alterar o arquivo enquanto ele está aberto é responsabilidade de quem chama, como em qualquer memory-map
+        let mmap = unsafe { Mmap::map(&file) }.map_err(|e| GraphError::io(path, e))?;
+        let bytes = &mmap[..];
+
+        if bytes.len() < TAMANHO_CABECALHO || &bytes[0..8] != MAGICO {
+            return Err(invalido("o arquivo não começa com o cabeçalho do snapshot".to_string()));
+        }
+        let versao = u32::from_le_bytes(bytes[8..12].try_into().expect("4 bytes"));
+        if versao != VERSAO {
+            return Err(invalido(format!("versão {} não suportada (esperada {})", versao, VERSAO)));
+        }
+        if bytes[12] != W::TAG {
+            return Err(invalido(format!("o peso foi gravado com o tipo {} e está sendo lido com o tipo {}", bytes[12], W::TAG)));
+        }
+        let flags = bytes[13];
+        let parallel_edges = match bytes[14] {
+            0 => ParallelEdges::KeepAll,
+            1 => ParallelEdges::KeepMin,
+            2 => ParallelEdges::KeepLast,
+            3 => ParallelEdges::Reject,
+            outro => return Err(invalido(format!("política de arestas paralelas desconhecida ({})", outro))),
+        };
+        let campo = |i: usize| u64_em(bytes, 16 + 8 * i) as usize;
+        let (num_vertex, linhas, arcos, num_edges, num_atributos, tamanho_meta) = (campo(0), campo(1), campo(2), campo(3), campo(4), campo(5));
+
+        // Tamanho esperado de cada seção, sem estourar com valores corrompidos
+        let tamanho_coluna = arcos.checked_mul(W::SIZE).map(|t| t.next_multiple_of(8));
+        let inicio_offsets = TAMANHO_CABECALHO.checked_add(tamanho_meta);
+        let inicio_targets = inicio_offsets.zip(linhas.checked_add(1).and_then(|l| l.checked_mul(8))).and_then(|(a, b)| a.checked_add(b));
+        let inicio_atributos = inicio_targets.zip(arcos.checked_mul(8)).and_then(|(a, b)| a.checked_add(b));
+        let total = inicio_atributos.zip(tamanho_coluna.and_then(|t| t.checked_mul(num_atributos))).and_then(|(a, b)| a.checked_add(b));
+        let (Some(inicio_offsets), Some(inicio_targets), Some(inicio_atributos), Some(total)) = (inicio_offsets, inicio_targets, inicio_atributos, total) else {
+            return Err(invalido("tamanhos do cabeçalho impossíveis".to_string()));
+        };
+        if total != bytes.len() || num_atributos == 0 || tamanho_meta % 8 != 0 {
+            return Err(invalido(format!("o arquivo tem {} bytes, mas o cabeçalho descreve {}", bytes.len(), total)));
+        }
+
+        if verificar && checksum(&bytes[TAMANHO_CABECALHO..]) != u64_em(bytes, 64) {
+            return Err(invalido("checksum não confere, o arquivo está corrompido".to_string()));
+        }
+
+        // Metadados
+        let mut meta = Leitor { bytes: &bytes[TAMANHO_CABECALHO..inicio_offsets], posicao: 0 };
+        let truncado = || invalido("metadados truncados".to_string());
+        let mut attribute_names = Vec::with_capacity(num_atributos);
+        for _ in 0..num_atributos {
+            attribute_names.push(meta.texto().ok_or_else(truncado)?);
+        }
+        let num_labels = meta.u64().ok_or_else(truncado)?;
+        let mut labels = Labels::new();
+        for _ in 0..num_labels {
+            let vertice = meta.u64().ok_or_else(truncado)? as usize;
+            if vertice >= num_vertex {
+                return Err(invalido(format!("o nome de vértice {} está fora dos {} vértices", vertice, num_vertex)));
+            }
+            labels.insert(vertice, &meta.texto().ok_or_else(truncado)?);
+        }
+
+        let snapshot = Snapshot {
+            is_zero_based: flags & 1 != 0,
+            is_directed: flags & 2 != 0,
+            num_vertex,
+            num_edges,
+            parallel_edges,
+            attribute_names,
+            labels: if labels.is_empty() { None } else { Some(labels) },
+            mmap,
+            linhas,
+            arcos,
+            inicio_offsets,
+            inicio_targets,
+            inicio_atributos,
+            _peso: PhantomData,
+        };
+
+        // Offsets crescentes garantem que toda lista de vizinhos está dentro do arquivo
+        let mut anterior = 0;
+        for v in 0..=linhas {
+            let offset = snapshot.offset(v);
+            if offset < anterior || offset > arcos || (v == 0 && offset != 0) || (v == linhas && offset != arcos) {
+                return Err(invalido(format!("offset inválido na linha {}", v)));
+            }
+            anterior = offset;
+        }
+        if verificar && let Some(i) = (0..arcos).find(|&i| snapshot.target(i) >= linhas) {
+            return Err(invalido(format!("o arco {} aponta para o vértice {}, fora das {} linhas", i, snapshot.target(i), linhas)));
+        }
+
+        Ok(snapshot)
+    }
+
+    /// # Início da lista de vizinhos da linha v
+
+    fn offset(&self, v: usize) -> usize {
+        u64_em(&self.mmap, self.inicio_offsets + 8 * v) as usize
+    }
+
+    /// # Destino do arco i
+
+    fn target(&self, i: usize) -> usize {
+        u64_em(&self.mmap, self.inicio_targets + 8 * i) as usize
+    }
+
+    /// # Valor do atributo (na ordem de attribute_names) do arco i
+
+    fn valor(&self, atributo: usize, i: usize) -> W {
+        let coluna = self.inicio_atributos + atributo * (self.arcos * W::SIZE).next_multiple_of(8);
+        W::read_le(&self.mmap[coluna + i * W::SIZE..coluna + (i + 1) * W::SIZE])
+    }
+
+    /// # Quantidade de arcos guardados (arestas não-direcionadas contam duas vezes, como no CSR)
+
+    pub fn num_arcs(&self) -> usize {
+        self.arcos
+    }
+
+    /// # Copia o snapshot para um CsrGraph na memória
+
+    pub fn to_csr(&self) -> CsrGraph<W> {
+        CsrGraph {
+            is_zero_based: self.is_zero_based,
+            is_directed: self.is_directed,
+            num_vertex: self.num_vertex,
+            offsets: (0..=self.linhas).map(|v| self.offset(v)).collect(),
+            targets: (0..self.arcos).map(|i| self.target(i)).collect(),
+            weights: (0..self.arcos).map(|i| self.valor(0, i)).collect(),
+            extra: (1..self.attribute_names.len()).map(|a| (0..self.arcos).map(|i| self.valor(a, i)).collect()).collect(),
+            labels: self.labels.clone(),
+            attribute_names: self.attribute_names.clone(),
+        }
+    }
+
+    /// # Reconstrói o Graph completo (com todos os atributos e nomes), para quem precisa alterá-lo
+
+    pub fn to_graph(&self) -> Graph<W> {
+        let nomes: Vec<&str> = self.attribute_names.iter().map(|nome| nome.as_str()).collect();
+        let mut grafo = Graph::with_attributes(self.is_zero_based, self.num_vertex, &nomes);
+
+        // Os arcos guardados já têm os dois sentidos das arestas não-direcionadas
+        let mut atributos = vec![W::zero(); nomes.len()];
+        for v in 0..self.linhas {
+            for i in self.offset(v)..self.offset(v + 1) {
+                for (a, valor) in atributos.iter_mut().enumerate() {
+                    *valor = self.valor(a, i);
+                }
+                grafo.incluir_na_leitura(v, self.target(i), &atributos);
+            }
+        }
+
+        grafo.terminar_leitura();
+        grafo.is_directed = self.is_directed;
+        grafo.num_edges = self.num_edges;
+        grafo.parallel_edges = self.parallel_edges;
+        grafo.labels = self.labels.clone();
+        grafo
+    }
+}
+
+impl<W: SnapshotWeight> WeightedGraph<W> for Snapshot<W> {
+    fn num_vertex(&self) -> usize {
+        self.num_vertex
+    }
+
+    fn neighbors_with_weights(&self, vertice: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        let intervalo = if vertice < self.linhas { self.offset(vertice)..self.offset(vertice + 1) } else { 0..0 };
+        intervalo.map(|i| (self.target(i), self.valor(0, i)))
+    }
+}
+
+/// # Leitor dos metadados, que retorna None em vez de ler fora do intervalo
+
+struct Leitor<'a> {
+    bytes: &'a [u8],
+    posicao: usize,
+}
+
+impl Leitor<'_> {
+    fn pegar(&mut self, n: usize) -> Option<&[u8]> {
+        let fim = self.posicao.checked_add(n).filter(|fim| *fim <= self.bytes.len())?;
+        let pedaco = &self.bytes[self.posicao..fim];
+        self.posicao = fim;
+        Some(pedaco)
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.pegar(8).map(|b| u64::from_le_bytes(b.try_into().expect("8 bytes")))
+    }
+
+    fn texto(&mut self) -> Option<String> {
+        let tamanho = u32::from_le_bytes(self.pegar(4)?.try_into().expect("4 bytes")) as usize;
+        self.pegar(tamanho).and_then(|b| String::from_utf8(b.to_vec()).ok())
+    }
+}
+
+/// # Escritor do corpo do snapshot, que calcula o checksum enquanto escreve
+/// Guarda só um bloco pequeno na memória: as palavras completas de 8 bytes vão para o destino (e para o checksum) a cada TAMANHO_BLOCO bytes.
+
+struct CorpoComChecksum<'a, O> {
+    saida: &'a mut O,
+    buffer: Vec<u8>,
+    hash: u64,
+    tamanho: usize,
+}
+
+impl<'a, O: Write> CorpoComChecksum<'a, O> {
+    const TAMANHO_BLOCO: usize = 1 << 16;
+
+    fn new(saida: &'a mut O) -> Self {
+        CorpoComChecksum { saida, buffer: Vec::with_capacity(Self::TAMANHO_BLOCO + 32), hash: INICIO_CHECKSUM, tamanho: 0 }
+    }
+
+    fn escrever(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.buffer.extend_from_slice(bytes);
+        self.tamanho += bytes.len();
+        self.talvez_descarregar()
+    }
+
+    fn peso<W: SnapshotWeight>(&mut self, peso: W) -> io::Result<()> {
+        peso.write_le(&mut self.buffer);
+        self.tamanho += W::SIZE;
+        self.talvez_descarregar()
+    }
+
+    /// # Grava um texto como (tamanho u32, bytes UTF-8)
+
+    fn texto(&mut self, texto: &str) -> io::Result<()> {
+        self.escrever(&(texto.len() as u32).to_le_bytes())?;
+        self.escrever(texto.as_bytes())
+    }
+
+    /// # Completa com zeros até um múltiplo de 8 bytes
+
+    fn alinhar(&mut self) -> io::Result<()> {
+        let falta = self.tamanho.next_multiple_of(8) - self.tamanho;
+        self.escrever(&[0; 8][..falta])
+    }
+
+    fn talvez_descarregar(&mut self) -> io::Result<()> {
+        if self.buffer.len() >= Self::TAMANHO_BLOCO { self.descarregar() } else { Ok(()) }
+    }
+
+    /// # Escreve as palavras completas do buffer, deixando o resto (menos de 8 bytes) para a próxima vez
+
+    fn descarregar(&mut self) -> io::Result<()> {
+        let completas = self.buffer.len() - self.buffer.len() % 8;
+        self.hash = misturar(self.hash, &self.buffer[..completas]);
+        self.saida.write_all(&self.buffer[..completas])?;
+        self.buffer.drain(..completas);
+        Ok(())
+    }
+
+    /// # Escreve o que falta e retorna o checksum e o tamanho do corpo (sempre múltiplo de 8, já que cada seção termina alinhada)
+
+    fn terminar(mut self) -> io::Result<(u64, usize)> {
+        self.descarregar()?;
+        debug_assert!(self.buffer.is_empty(), "o corpo termina alinhado em 8 bytes");
+        Ok((self.hash, self.tamanho))
+    }
+}
+
+/// # Lê um u64 little-endian na posição indicada
+
+fn u64_em(bytes: &[u8], posicao: usize) -> u64 {
+    u64::from_le_bytes(bytes[posicao..posicao + 8].try_into().expect("8 bytes"))
+}
+
+const INICIO_CHECKSUM: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// # Checksum de 64 bits (FNV-1a aplicado a palavras de 8 bytes, o corpo sempre tem tamanho múltiplo de 8)
+
+fn checksum(bytes: &[u8]) -> u64 {
+    misturar(INICIO_CHECKSUM, bytes)
+}
+
+/// # Continua o checksum com mais bytes (em palavras de 8 bytes; os do CorpoComChecksum sempre chegam em palavras completas)
+
+fn misturar(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.chunks(8).fold(hash, |hash, palavra| {
+        let mut buffer = [0u8; 8];
+        buffer[..palavra.len()].copy_from_slice(palavra);
+        (hash ^ u64::from_le_bytes(buffer)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::tests::{arestas_com_atributos, arquivo_temporario};
+    use std::io::Cursor;
+
+    /// # Bytes do snapshot de um grafo direcionado 0-based com dois atributos e um vértice com nome
+
+    fn snapshot_de_exemplo() -> (Graph, Vec<u8>) {
+        let mut gr: Graph = Graph::with_attributes(true, 4, &["distance", "time"]);
+        gr.edge_with_attributes(0, 1, &[5, 3]).unwrap();
+        gr.edge_with_attributes(0, 2, &[-1, 7]).unwrap();
+        gr.edge_with_attributes(2, 3, &[4, 0]).unwrap();
+        gr.set_label(3, "Estação Sé");
+
+        let mut saida = Cursor::new(Vec::new());
+        write_snapshot_to(&gr, &mut saida).unwrap();
+        (gr, saida.into_inner())
+    }
+
+    fn motivo<T: std::fmt::Debug>(resultado: Result<T, GraphError>) -> String {
+        match resultado {
+            Err(GraphError::InvalidSnapshot { reason, .. }) => reason,
+            outro => panic!("esperado um snapshot inválido, veio {:?}", outro),
+        }
+    }
+
+    #[test]
+    fn snapshot_ida_e_volta() {
+        let (gr, bytes) = snapshot_de_exemplo();
+        let snap: Snapshot<i32> = Snapshot::open(&arquivo_temporario("ida.snap", &bytes)).unwrap();
+
+        assert_eq!((snap.is_zero_based, snap.is_directed, snap.num_vertex, snap.num_edges, snap.num_arcs()), (true, true, 4, 3, 3));
+        assert_eq!(snap.attribute_names, gr.attribute_names);
+        assert_eq!(snap.neighbors_with_weights(0).collect::<Vec<_>>(), gr.neighbors_with_weights(0).collect::<Vec<_>>());
+
+        let lido = snap.to_graph();
+        assert_eq!(arestas_com_atributos(&lido), arestas_com_atributos(&gr));
+        assert_eq!((lido.label(0), lido.label(3)), (None, Some("Estação Sé")));
+        assert_eq!(snap.to_csr().extra, vec![vec![3, 7, 0]]);
+    }
+
+    #[test]
+    fn byte_trocado_no_corpo_e_recusado_pelo_checksum() {
+        let (_, mut bytes) = snapshot_de_exemplo();
+        let ultimo = bytes.len() - 1;
+        bytes[ultimo] ^= 0xff;
+        let path = arquivo_temporario("corrompido.snap", &bytes);
+
+        assert!(motivo(Snapshot::<i32>::open(&path)).contains("checksum"));
+        // Sem a verificação o arquivo abre (o byte trocado é do alinhamento da última coluna)
+        assert!(Snapshot::<i32>::open_unverified(&path).is_ok());
+    }
+
+    #[test]
+    fn tipo_de_peso_diferente_e_recusado() {
+        let (_, bytes) = snapshot_de_exemplo();
+        let path = arquivo_temporario("tipo.snap", &bytes);
+
+        assert!(motivo(Snapshot::<f64>::open(&path)).contains("tipo"));
+        assert!(motivo(Snapshot::<i64>::open(&path)).contains("tipo"));
+        assert!(Snapshot::<i32>::open(&path).is_ok());
+    }
+
+    #[test]
+    fn arquivo_truncado_ou_sem_cabecalho_e_recusado() {
+        let (_, bytes) = snapshot_de_exemplo();
+        assert!(motivo(Snapshot::<i32>::open(&arquivo_temporario("truncado.snap", &bytes[..bytes.len() - 8]))).contains("bytes"));
+        assert!(motivo(Snapshot::<i32>::open(&arquivo_temporario("texto.snap", b"0 1 5\n"))).contains("cabeçalho"));
+    }
+}
@@ -0,0 +1,263 @@
+//! # Estatísticas e relatório da estrutura do grafo
+//! ## Conteúdo:
+//! Resumo dos graus (DegreeStats);\
+//! Relatório com as contagens, graus, densidade, pesos, simetria e componentes (GraphReport);\
+//! Geração do relatório (Graph::report()) e exportação em txt (write_report()).
+//!
+//! Antes de escolher o algoritmo vale saber o que foi carregado: pesos negativos descartam o dijikstra(), um grafo desconexo deixa distâncias infinitas e as arestas repetidas descartadas na leitura (KeepMin, KeepLast) aparecem na diferença entre o cabeçalho e o encontrado:
+//! ```rust
+//!    let relatorio = gr.report();
+//!    println!("{}", relatorio);
+//!    write_report(&relatorio, "relatorio.txt")?;
+//!    write_json(&relatorio, "relatorio.json")?; // com a feature "serde"
+//! ```
+
+use std::fmt;
+
+pub use crate::error::GraphError;
+pub use crate::graph::{Graph, BufWriter, File, Write};
+pub use crate::weight::Weight;
+
+/// # Resumo de um grau (de saída ou de entrada) em todos os vértices.
+/// ## Atributos
+/// "min" e "max" - Menor e maior grau;\
+/// "mean" - Grau médio.
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DegreeStats {
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+}
+
+/// # Relatório da estrutura do grafo.
+/// ## Atributos
+/// "declared_vertices" - num_vertex do grafo (o do cabeçalho do arquivo);\
+/// "vertices_with_edges" - Vértices que aparecem em pelo menos uma aresta;\
+/// "out_of_range_vertices" - Vértices das arestas que passam de num_vertex (Graph::edge() não checa o intervalo);\
+/// "declared_edges" - Arestas declaradas no cabeçalho do arquivo (Graph::declared_edges), ou None se o grafo não veio de um txt ou DIMACS;\
+/// "counted_edges" - Arestas realmente guardadas (em grafos não-direcionados, cada uma conta uma vez);\
+/// "is_directed" - Se o grafo tem direção;\
+/// "out_degree" e "in_degree" - Graus de saída e de entrada (iguais em grafos não-direcionados);\
+/// "degree_histogram" - Pares (grau de saída, quantidade de vértices), em ordem crescente de grau;\
+/// "density" - Arestas encontradas dividido pelo máximo possível sem laços e arestas paralelas;\
+/// "negative_weights" - Arestas com peso negativo;\
+/// "self_loops" - Laços (aresta de um vértice para ele mesmo);\
+/// "parallel_edges" - Arestas repetidas além da primeira entre a mesma origem e o mesmo destino;\
+/// "is_symmetric" - Se toda aresta (u, v) tem a volta (v, u) com o mesmo peso, ou seja, se o grafo é na prática não-direcionado;\
+/// "weakly_connected_components" - Componentes ignorando a direção das arestas;\
+/// "strongly_connected_components" - Componentes em que todo vértice alcança todos os outros;\
+/// "isolated_vertices" - Vértices sem nenhuma aresta.
+///
+/// Os graus, a densidade e os componentes contam todos os vértices de 0 até num_vertex (ou até o maior vértice das arestas, se passar).
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GraphReport {
+    pub declared_vertices: usize,
+    pub vertices_with_edges: usize,
+    pub out_of_range_vertices: usize,
+    pub declared_edges: Option<usize>,
+    pub counted_edges: usize,
+    pub is_directed: bool,
+    pub out_degree: DegreeStats,
+    pub in_degree: DegreeStats,
+    pub degree_histogram: Vec<(usize, usize)>,
+    pub density: f64,
+    pub negative_weights: usize,
+    pub self_loops: usize,
+    pub parallel_edges: usize,
+    pub is_symmetric: bool,
+    pub weakly_connected_components: usize,
+    pub strongly_connected_components: usize,
+    pub isolated_vertices: usize,
+}
+
+impl<W: Weight> Graph<W> {
+
+    /// # Gera o relatório da estrutura do grafo
+    /// Percorre as arestas algumas vezes (O(V + E)), mais a checagem de simetria, que procura a volta de cada aresta na lista do destino.
+
+    pub fn report(&self) -> GraphReport {
+        let arestas: Vec<(usize, usize, W)> = self.edges().collect();
+        let n = arestas.iter().map(|&(origem, destino, _)| origem.max(destino) + 1).max().unwrap_or(0).max(self.num_vertex);
+
+        let mut grau_saida = vec![0; n];
+        let mut grau_entrada = vec![0; n];
+        let mut com_arestas = vec![false; n];
+        for &(origem, destino, _) in &arestas {
+            grau_saida[origem] += 1;
+            grau_entrada[destino] += 1;
+            if !self.is_directed && origem != destino {
+                grau_saida[destino] += 1;
+                grau_entrada[origem] += 1;
+            }
+            com_arestas[origem] = true;
+            com_arestas[destino] = true;
+        }
+
+        let mut histograma: Vec<(usize, usize)> = Vec::new();
+        let mut graus_ordenados = grau_saida.clone();
+        graus_ordenados.sort_unstable();
+        for grau in graus_ordenados {
+            match histograma.last_mut() {
+                Some((ultimo, quantidade)) if *ultimo == grau => *quantidade += 1,
+                _ => histograma.push((grau, 1)),
+            }
+        }
+
+        let mut pares: Vec<(usize, usize)> = arestas.iter().map(|&(origem, destino, _)| (origem, destino)).collect();
+        pares.sort_unstable();
+        let paralelas = pares.windows(2).filter(|par| par[0] == par[1]).count();
+
+        let maximo = if self.is_directed { n * n.saturating_sub(1) } else { n * n.saturating_sub(1) / 2 };
+        let fracas = componentes_fracas(n, &arestas);
+
+        GraphReport {
+            declared_vertices: self.num_vertex,
+            vertices_with_edges: com_arestas.iter().filter(|&&tem| tem).count(),
+            out_of_range_vertices: com_arestas.iter().skip(self.num_vertex).filter(|&&tem| tem).count(),
+            declared_edges: self.declared_edges,
+            counted_edges: arestas.len(),
+            is_directed: self.is_directed,
+            out_degree: resumo(&grau_saida),
+            in_degree: resumo(&grau_entrada),
+            degree_histogram: histograma,
+            density: if maximo == 0 { 0.0 } else { arestas.len() as f64 / maximo as f64 },
+            negative_weights: arestas.iter().filter(|&&(_, _, peso)| peso < W::zero()).count(),
+            self_loops: arestas.iter().filter(|&&(origem, destino, _)| origem == destino).count(),
+            parallel_edges: paralelas,
+            is_symmetric: !self.is_directed
+                || arestas.iter().all(|&(origem, destino, peso)| self.parallel_edges(destino, origem).any(|node| node.weight == peso)),
+            weakly_connected_components: fracas,
+            strongly_connected_components: if self.is_directed { self.componentes_fortes(n) } else { fracas },
+            isolated_vertices: com_arestas.iter().filter(|&&tem| !tem).count(),
+        }
+    }
+
+    /// # Quantidade de componentes fortemente conexas (Kosaraju)
+    /// 1: ordem de término de uma busca em profundidade no grafo;\
+    /// 2: busca no grafo reverso (transpose()) na ordem inversa de término, cada árvore nova é uma componente.\
+    /// As buscas usam uma pilha própria, para não estourar a pilha de chamadas em grafos grandes.
+
+    fn componentes_fortes(&self, n: usize) -> usize {
+        let mut visitado = vec![false; n];
+        let mut ordem = Vec::with_capacity(n);
+        for inicio in 0..n {
+            if visitado[inicio] {
+                continue;
+            }
+            visitado[inicio] = true;
+            let mut pilha = vec![(inicio, self.neighbors_with_weights(inicio))];
+            while let Some((vertice, vizinhos)) = pilha.last_mut() {
+                let vertice = *vertice;
+                match vizinhos.next() {
+                    Some((vizinho, _)) if !visitado[vizinho] => {
+                        visitado[vizinho] = true;
+                        pilha.push((vizinho, self.neighbors_with_weights(vizinho)));
+                    }
+                    Some(_) => {}
+                    None => {
+                        ordem.push(vertice);
+                        pilha.pop();
+                    }
+                }
+            }
+        }
+
+        let reverso = self.transpose();
+        let mut visitado = vec![false; n];
+        let mut componentes = 0;
+        for inicio in ordem.into_iter().rev() {
+            if visitado[inicio] {
+                continue;
+            }
+            componentes += 1;
+            visitado[inicio] = true;
+            let mut pilha = vec![inicio];
+            while let Some(vertice) = pilha.pop() {
+                for (vizinho, _) in reverso.neighbors_with_weights(vertice) {
+                    if !visitado[vizinho] {
+                        visitado[vizinho] = true;
+                        pilha.push(vizinho);
+                    }
+                }
+            }
+        }
+        componentes
+    }
+}
+
+/// # Mínimo, máximo e média dos graus
+fn resumo(graus: &[usize]) -> DegreeStats {
+    DegreeStats {
+        min: graus.iter().copied().min().unwrap_or(0),
+        max: graus.iter().copied().max().unwrap_or(0),
+        mean: if graus.is_empty() { 0.0 } else { graus.iter().sum::<usize>() as f64 / graus.len() as f64 },
+    }
+}
+
+/// # Quantidade de componentes ignorando a direção (union-find com compressão de caminho)
+fn componentes_fracas<W>(n: usize, arestas: &[(usize, usize, W)]) -> usize {
+    let mut pai: Vec<usize> = (0..n).collect();
+    let mut componentes = n;
+    for &(origem, destino, _) in arestas {
+        let (a, b) = (raiz(&mut pai, origem), raiz(&mut pai, destino));
+        if a != b {
+            pai[a] = b;
+            componentes -= 1;
+        }
+    }
+    componentes
+}
+
+/// # Representante da componente do vértice, encurtando o caminho no union-find
+fn raiz(pai: &mut [usize], mut v: usize) -> usize {
+    while pai[v] != v {
+        pai[v] = pai[pai[v]];
+        v = pai[v];
+    }
+    v
+}
+
+impl fmt::Display for DegreeStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "mín {}, máx {}, média {:.2}", self.min, self.max, self.mean)
+    }
+}
+
+impl fmt::Display for GraphReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sim_nao = |valor: bool| if valor { "sim" } else { "não" };
+        writeln!(f, "Vértices: {} declarados, {} com arestas, {} fora do intervalo, {} isolados", self.declared_vertices, self.vertices_with_edges, self.out_of_range_vertices, self.isolated_vertices)?;
+        let direcao = if self.is_directed { "direcionado" } else { "não-direcionado" };
+        match self.declared_edges {
+            Some(declaradas) => writeln!(f, "Arestas: {} declaradas no cabeçalho, {} encontradas ({})", declaradas, self.counted_edges, direcao)?,
+            None => writeln!(f, "Arestas: {} encontradas ({})", self.counted_edges, direcao)?,
+        }
+        writeln!(f, "Grau de saída: {}", self.out_degree)?;
+        writeln!(f, "Grau de entrada: {}", self.in_degree)?;
+        let histograma: Vec<String> = self.degree_histogram.iter().map(|(grau, quantidade)| format!("{}: {}", grau, quantidade)).collect();
+        writeln!(f, "Distribuição do grau de saída (grau: vértices): {}", histograma.join(", "))?;
+        writeln!(f, "Densidade: {:.4}", self.density)?;
+        writeln!(f, "Pesos negativos: {}", self.negative_weights)?;
+        writeln!(f, "Laços: {}", self.self_loops)?;
+        writeln!(f, "Arestas paralelas: {}", self.parallel_edges)?;
+        writeln!(f, "Simétrico (na prática não-direcionado): {}", sim_nao(self.is_symmetric))?;
+        writeln!(f, "Componentes fracamente conexas: {}", self.weakly_connected_components)?;
+        write!(f, "Componentes fortemente conexas: {}", self.strongly_connected_components)
+    }
+}
+
+/// # Função que salva o relatório em um arquivo txt, no mesmo texto do println!("{}", relatorio).
+/// ## Retorno
+///
+/// Nada, ou um GraphError::Io com o caminho do arquivo.\
+/// Para exportar em JSON use write_json() (feature "serde").
+
+pub fn write_report(relatorio: &GraphReport, path: &str) -> Result<(), GraphError> {
+    let file = File::create(path).map_err(|e| GraphError::io(path, e))?;
+    let mut writer = BufWriter::new(file);
+    writeln!(writer, "{}", relatorio).and_then(|_| writer.flush()).map_err(|e| GraphError::io(path, e))
+}
@@ -0,0 +1,53 @@
+//! # Tipo genérico dos pesos das arestas
+//! ## Conteúdo:
+//! Trait que todo peso precisa implementar (Weight);\
+//! Implementações para os tipos numéricos primitivos.
+//!
+//! O grafo e os três algoritmos (Dijkstra, Bellman Ford e Floyd Warshall) são genéricos sobre esse trait, então dá para usar tempos fracionários (f64), energias grandes (i64) ou um tipo próprio.
+
+use std::fmt::{Debug, Display};
+use std::ops::Add;
+use std::str::FromStr;
+
+/// # Representa um tipo numérico que pode ser usado como peso de aresta.
+/// ## Requisitos
+/// "zero" - O elemento neutro da soma (custo de um vértice para ele mesmo);\
+/// "infinity" - O valor usado como INFINITO nos algoritmos. Nos inteiros é o máximo dividido por 2, para que a soma de dois infinitos não estoure.
+///
+/// ## Tipo próprio
+/// Para usar um tipo próprio basta implementar as operações exigidas e o trait:
+/// ```rust
+/// impl Weight for Segundos {
+///     fn zero() -> Self { Segundos(0.0) }
+///     fn infinity() -> Self { Segundos(f64::INFINITY) }
+/// }
+/// ```
+pub trait Weight: Copy + PartialOrd + Add<Output = Self> + Debug + Display + FromStr {
+    fn zero() -> Self;
+    fn infinity() -> Self;
+}
+
+macro_rules! impl_weight_int {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self { 0 }
+                fn infinity() -> Self { <$t>::MAX / 2 } //ta dividido por 2 pra evitar overflow
+            }
+        )*
+    };
+}
+
+macro_rules! impl_weight_float {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self { 0.0 }
+                fn infinity() -> Self { <$t>::INFINITY }
+            }
+        )*
+    };
+}
+
+impl_weight_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_weight_float!(f32, f64);
@@ -0,0 +1,573 @@
+//! # Leitura e escrita dos formatos XML do Gephi e do yEd
+//! ## Conteúdo:
+//! GraphML, usado pelo yEd (read_graphml(), write_graphml() e write_graphml_to());\
+//! GEXF, usado pelo Gephi (read_gexf(), write_gexf() e write_gexf_to()).
+//!
+//! Os dois formatos guardam a direção do grafo, os nomes dos vértices e os atributos das arestas, então qualquer grafo do read_graph() ou do map_to_txt() pode ser aberto nessas ferramentas sem conversores feitos à mão.
+//! ## Identificadores dos vértices
+//! No XML os vértices são identificados por texto. Na escrita usamos o número do vértice na indexação original (como no txt), e na leitura:
+//! ```rust
+//!    // ids "0".."n-1"      -> grafo 0-based, o vértice é o próprio id
+//!    // ids "1".."n"        -> grafo 1-based, o vértice é o id - 1
+//!    // qualquer outro caso -> vértices na ordem em que aparecem, e o id vira o nome do vértice (se ele não tiver um)
+//! ```
+
+use std::io;
+
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+pub use crate::error::{GraphError, ParseErrorKind};
+pub use crate::graph::{Graph, HashMap, BufWriter, File, Write, fs};
+pub use crate::weight::Weight;
+
+/// # Vértice lido do XML, antes de virar um vértice do grafo.
+/// ## Atributos
+/// "id" - O identificador do vértice no arquivo;\
+/// "label" - O nome do vértice, se existir;\
+/// "posicao" - Byte do arquivo onde o elemento começa (para as mensagens de erro).
+
+struct NoXml {
+    id: String,
+    label: Option<String>,
+    posicao: usize,
+}
+
+/// # Aresta lida do XML, antes de virar uma aresta do grafo.
+/// ## Atributos
+/// "origem" e "destino" - Os identificadores dos vértices, com a posição do elemento;\
+/// "valores" - O texto de cada atributo, na ordem de DocumentoXml::attribute_names (None usa o valor padrão).
+
+struct ArestaXml {
+    origem: String,
+    destino: String,
+    valores: Vec<Option<String>>,
+    posicao: usize,
+}
+
+/// # Conteúdo do arquivo XML, no formato comum aos dois leitores.
+
+struct DocumentoXml {
+    is_directed: bool,
+    attribute_names: Vec<String>,
+    padroes: Vec<Option<String>>,
+    nos: Vec<NoXml>,
+    arestas: Vec<ArestaXml>,
+}
+
+/// # Função que lê um grafo no formato GraphML.
+/// ## Argumentos
+///
+/// "path" - O caminho do arquivo .graphml.
+///
+/// ## Retorno
+///
+/// O grafo, ou o GraphError com a linha e a coluna do problema.
+/// ## Atributos
+/// Cada "key" com for="edge" vira um atributo das arestas (o de nome "weight", se existir, vira o peso), e a "key" de nó com nome "label" ou "name" vira o nome dos vértices.\
+/// Atributos sem valor na aresta usam o "default" da key, ou 1 no peso (como no GEXF) e zero nos demais.
+
+pub fn read_graphml<W: Weight>(path: &str) -> Result<Graph<W>, GraphError> {
+    let conteudo = fs::read_to_string(path).map_err(|e| GraphError::io(path, e))?;
+    let mut reader = Reader::from_str(&conteudo);
+    reader.config_mut().trim_text(true);
+
+    let mut documento = DocumentoXml { is_directed: true, attribute_names: Vec::new(), padroes: Vec::new(), nos: Vec::new(), arestas: Vec::new() };
+    let mut chaves_aresta: HashMap<String, usize> = HashMap::new();
+    let mut chave_label: Option<String> = None;
+    let mut achou_grafo = false;
+
+    // Contexto dos textos: a key cujo default está sendo lido, ou o data (e o elemento dono dele)
+    let mut key_atual: Option<usize> = None;
+    let mut lendo_default = false;
+    let mut dentro_de_no = false;
+    let mut dentro_de_aresta = false;
+    let mut data_atual: Option<String> = None;
+
+    loop {
+        let posicao = inicio_do_elemento(&conteudo, reader.buffer_position());
+        let evento = reader.read_event().map_err(|e| erro_xml(path, &conteudo, reader.error_position() as usize, e.to_string()))?;
+        let vazio = matches!(evento, Event::Empty(_));
+        match evento {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"key" => {
+                    let id = obrigatorio(path, &conteudo, posicao, &e, "id")?;
+                    let nome = atributo(&e, "attr.name").unwrap_or_else(|| id.clone());
+                    match atributo(&e, "for").as_deref() {
+                        Some("edge") => {
+                            chaves_aresta.insert(id, documento.attribute_names.len());
+                            documento.attribute_names.push(nome);
+                            documento.padroes.push(None);
+                            key_atual = if vazio { None } else { Some(documento.attribute_names.len() - 1) };
+                        }
+                        Some("node") if nome == "label" || nome == "name" => chave_label = Some(id),
+                        _ => {}
+                    }
+                }
+                b"default" => lendo_default = !vazio,
+                b"graph" => {
+                    achou_grafo = true;
+                    documento.is_directed = atributo(&e, "edgedefault").as_deref() != Some("undirected");
+                }
+                b"node" => {
+                    let id = obrigatorio(path, &conteudo, posicao, &e, "id")?;
+                    documento.nos.push(NoXml { id, label: None, posicao });
+                    dentro_de_no = !vazio;
+                }
+                b"edge" => {
+                    let origem = obrigatorio(path, &conteudo, posicao, &e, "source")?;
+                    let destino = obrigatorio(path, &conteudo, posicao, &e, "target")?;
+                    documento.arestas.push(ArestaXml { origem, destino, valores: Vec::new(), posicao });
+                    dentro_de_aresta = !vazio;
+                }
+                b"data" if !vazio => data_atual = atributo(&e, "key"),
+                _ => {}
+            },
+            Event::Text(texto) => {
+                let texto = texto.unescape().map_err(|e| erro_xml(path, &conteudo, posicao, e.to_string()))?.into_owned();
+                if lendo_default && let Some(i) = key_atual {
+                    documento.padroes[i] = Some(texto);
+                } else if let Some(chave) = &data_atual {
+                    if dentro_de_aresta && let Some(&i) = chaves_aresta.get(chave) {
+                        let aresta = documento.arestas.last_mut().expect("data dentro de uma aresta");
+                        if aresta.valores.len() <= i {
+                            aresta.valores.resize(i + 1, None);
+                        }
+                        aresta.valores[i] = Some(texto);
+                    } else if dentro_de_no && chave_label.as_ref() == Some(chave) {
+                        documento.nos.last_mut().expect("data dentro de um nó").label = Some(texto);
+                    }
+                }
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"key" => key_atual = None,
+                b"default" => lendo_default = false,
+                b"node" => dentro_de_no = false,
+                b"edge" => dentro_de_aresta = false,
+                b"data" => data_atual = None,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if !achou_grafo {
+        return Err(GraphError::parse(path, 1, 1, ParseErrorKind::EmptyFile));
+    }
+
+    // O atributo "weight" vira o peso (o primeiro atributo)
+    if let Some(i) = documento.attribute_names.iter().position(|nome| nome == "weight") {
+        for aresta in &mut documento.arestas {
+            aresta.valores.resize(documento.attribute_names.len(), None);
+            aresta.valores.swap(0, i);
+        }
+        documento.attribute_names.swap(0, i);
+        documento.padroes.swap(0, i);
+    }
+
+    // Sem key de peso (como nos arquivos sem peso do yEd), cada aresta custa 1, igual ao GEXF
+    if documento.attribute_names.is_empty() {
+        documento.attribute_names.push("weight".to_string());
+        documento.padroes.push(None);
+    }
+    if documento.padroes[0].is_none() {
+        documento.padroes[0] = Some("1".to_string());
+    }
+
+    montar_grafo(path, &conteudo, documento)
+}
+
+/// # Função que salva o grafo em um arquivo .graphml.
+/// ## Retorno
+///
+/// Nada, ou um GraphError::Io com o caminho do arquivo.
+
+pub fn write_graphml<W: Weight>(grafo: &Graph<W>, path: &str) -> Result<(), GraphError> {
+    let file = File::create(path).map_err(|e| GraphError::io(path, e))?;
+    let mut writer = BufWriter::new(file);
+    write_graphml_to(grafo, &mut writer).and_then(|_| writer.flush()).map_err(|e| GraphError::io(path, e))
+}
+
+/// # Função que escreve o grafo no formato GraphML em qualquer destino.
+/// Cada atributo das arestas vira uma "key" do tipo double, e os nomes dos vértices usam a key "label".\
+/// Em grafos não-direcionados cada aresta é escrita uma vez só, com edgedefault="undirected".
+
+pub fn write_graphml_to<W: Weight, O: Write>(grafo: &Graph<W>, writer: &mut O) -> io::Result<()> {
+    let ajuste = if grafo.is_zero_based { 0 } else { 1 };
+    let direcao = if grafo.is_directed { "directed" } else { "undirected" };
+
+    writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(writer, "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">")?;
+    writeln!(writer, "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>")?;
+    for (i, nome) in grafo.attribute_names.iter().enumerate() {
+        writeln!(writer, "  <key id=\"e{}\" for=\"edge\" attr.name=\"{}\" attr.type=\"double\"/>", i, escapar(nome))?;
+    }
+    writeln!(writer, "  <graph id=\"G\" edgedefault=\"{}\">", direcao)?;
+
+    for v in 0..grafo.num_vertex {
+        match grafo.label(v) {
+            Some(nome) => writeln!(writer, "    <node id=\"{}\"><data key=\"label\">{}</data></node>", v + ajuste, escapar(nome))?,
+            None => writeln!(writer, "    <node id=\"{}\"/>", v + ajuste)?,
+        }
+    }
+
+    for (origem, node) in grafo.arestas_unicas() {
+        let dados: Vec<String> = (0..grafo.attribute_names.len())
+            .map(|i| format!("<data key=\"e{}\">{}</data>", i, node.attribute(i).unwrap_or(W::zero())))
+            .collect();
+        writeln!(writer, "    <edge source=\"{}\" target=\"{}\">{}</edge>", origem + ajuste, node.value + ajuste, dados.join(""))?;
+    }
+
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</graphml>")
+}
+
+/// # Função que lê um grafo no formato GEXF.
+/// ## Argumentos
+///
+/// "path" - O caminho do arquivo .gexf.
+///
+/// ## Retorno
+///
+/// O grafo, ou o GraphError com a linha e a coluna do problema.
+/// ## Atributos
+/// O "weight" de cada aresta vira o peso (1 se não existir, como no Gephi), e cada "attribute" da classe "edge" vira mais um atributo, com o "title" como nome.\
+/// O "label" de cada nó vira o nome do vértice.
+
+pub fn read_gexf<W: Weight>(path: &str) -> Result<Graph<W>, GraphError> {
+    let conteudo = fs::read_to_string(path).map_err(|e| GraphError::io(path, e))?;
+    let mut reader = Reader::from_str(&conteudo);
+    reader.config_mut().trim_text(true);
+
+    let mut documento = DocumentoXml { is_directed: true, attribute_names: vec!["weight".to_string()], padroes: vec![Some("1".to_string())], nos: Vec::new(), arestas: Vec::new() };
+    let mut atributos_aresta: HashMap<String, usize> = HashMap::new();
+    let mut achou_grafo = false;
+
+    let mut classe_aresta = false;
+    let mut atributo_atual: Option<usize> = None;
+    let mut lendo_default = false;
+    let mut dentro_de_aresta = false;
+
+    loop {
+        let posicao = inicio_do_elemento(&conteudo, reader.buffer_position());
+        let evento = reader.read_event().map_err(|e| erro_xml(path, &conteudo, reader.error_position() as usize, e.to_string()))?;
+        let vazio = matches!(evento, Event::Empty(_));
+        match evento {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"graph" => {
+                    achou_grafo = true;
+                    documento.is_directed = atributo(&e, "defaultedgetype").as_deref() != Some("undirected");
+                }
+                b"attributes" => classe_aresta = atributo(&e, "class").as_deref() == Some("edge"),
+                b"attribute" if classe_aresta => {
+                    let id = obrigatorio(path, &conteudo, posicao, &e, "id")?;
+                    let nome = atributo(&e, "title").unwrap_or_else(|| id.clone());
+                    atributos_aresta.insert(id, documento.attribute_names.len());
+                    documento.attribute_names.push(nome);
+                    documento.padroes.push(None);
+                    atributo_atual = if vazio { None } else { Some(documento.attribute_names.len() - 1) };
+                }
+                b"default" => lendo_default = !vazio,
+                b"node" => {
+                    let id = obrigatorio(path, &conteudo, posicao, &e, "id")?;
+                    documento.nos.push(NoXml { id, label: atributo(&e, "label"), posicao });
+                }
+                b"edge" => {
+                    let origem = obrigatorio(path, &conteudo, posicao, &e, "source")?;
+                    let destino = obrigatorio(path, &conteudo, posicao, &e, "target")?;
+                    documento.arestas.push(ArestaXml { origem, destino, valores: vec![atributo(&e, "weight")], posicao });
+                    dentro_de_aresta = !vazio;
+                }
+                b"attvalue" if dentro_de_aresta => {
+                    let chave = obrigatorio(path, &conteudo, posicao, &e, "for")?;
+                    if let Some(&i) = atributos_aresta.get(&chave) {
+                        let aresta = documento.arestas.last_mut().expect("attvalue dentro de uma aresta");
+                        if aresta.valores.len() <= i {
+                            aresta.valores.resize(i + 1, None);
+                        }
+                        aresta.valores[i] = atributo(&e, "value");
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(texto) if lendo_default => {
+                if let Some(i) = atributo_atual {
+                    let texto = texto.unescape().map_err(|e| erro_xml(path, &conteudo, posicao, e.to_string()))?;
+                    documento.padroes[i] = Some(texto.into_owned());
+                }
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"attributes" => classe_aresta = false,
+                b"attribute" => atributo_atual = None,
+                b"default" => lendo_default = false,
+                b"edge" => dentro_de_aresta = false,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if !achou_grafo {
+        return Err(GraphError::parse(path, 1, 1, ParseErrorKind::EmptyFile));
+    }
+
+    montar_grafo(path, &conteudo, documento)
+}
+
+/// # Função que salva o grafo em um arquivo .gexf.
+/// ## Retorno
+///
+/// Nada, ou um GraphError::Io com o caminho do arquivo.
+
+pub fn write_gexf<W: Weight>(grafo: &Graph<W>, path: &str) -> Result<(), GraphError> {
+    let file = File::create(path).map_err(|e| GraphError::io(path, e))?;
+    let mut writer = BufWriter::new(file);
+    write_gexf_to(grafo, &mut writer).and_then(|_| writer.flush()).map_err(|e| GraphError::io(path, e))
+}
+
+/// # Função que escreve o grafo no formato GEXF 1.3 em qualquer destino.
+/// O peso vai no "weight" da aresta e os demais atributos em "attvalues", declarados na classe "edge".\
+/// O GEXF não tem onde guardar o nome do primeiro atributo, então ele volta como "weight" na leitura.
+
+pub fn write_gexf_to<W: Weight, O: Write>(grafo: &Graph<W>, writer: &mut O) -> io::Result<()> {
+    let ajuste = if grafo.is_zero_based { 0 } else { 1 };
+    let direcao = if grafo.is_directed { "directed" } else { "undirected" };
+
+    writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(writer, "<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">")?;
+    writeln!(writer, "  <graph mode=\"static\" defaultedgetype=\"{}\">", direcao)?;
+
+    if grafo.attribute_names.len() > 1 {
+        writeln!(writer, "    <attributes class=\"edge\">")?;
+        for (i, nome) in grafo.attribute_names.iter().enumerate().skip(1) {
+            writeln!(writer, "      <attribute id=\"{}\" title=\"{}\" type=\"double\"/>", i, escapar(nome))?;
+        }
+        writeln!(writer, "    </attributes>")?;
+    }
+
+    writeln!(writer, "    <nodes>")?;
+    for v in 0..grafo.num_vertex {
+        writeln!(writer, "      <node id=\"{}\" label=\"{}\"/>", v + ajuste, escapar(&grafo.vertex_name(v)))?;
+    }
+    writeln!(writer, "    </nodes>")?;
+
+    writeln!(writer, "    <edges>")?;
+    for (id, (origem, node)) in grafo.arestas_unicas().enumerate() {
+        let inicio = format!("<edge id=\"{}\" source=\"{}\" target=\"{}\" weight=\"{}\"", id, origem + ajuste, node.value + ajuste, node.weight);
+        if grafo.attribute_names.len() == 1 {
+            writeln!(writer, "      {}/>", inicio)?;
+        } else {
+            let valores: Vec<String> = (1..grafo.attribute_names.len())
+                .map(|i| format!("<attvalue for=\"{}\" value=\"{}\"/>", i, node.attribute(i).unwrap_or(W::zero())))
+                .collect();
+            writeln!(writer, "      {}><attvalues>{}</attvalues></edge>", inicio, valores.join(""))?;
+        }
+    }
+    writeln!(writer, "    </edges>")?;
+
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</gexf>")
+}
+
+/// # Transforma o conteúdo lido do XML no grafo, checando os vértices e os valores das arestas
+
+fn montar_grafo<W: Weight>(path: &str, conteudo: &str, documento: DocumentoXml) -> Result<Graph<W>, GraphError> {
+    let erro = |posicao: usize, kind: ParseErrorKind| {
+        let (linha, coluna) = linha_e_coluna(conteudo, posicao);
+        GraphError::parse(path, linha, coluna, kind)
+    };
+
+    // Identificador -> vértice, conforme a regra da documentação do módulo
+    let mut vertices: HashMap<String, usize> = HashMap::new();
+    for (i, no) in documento.nos.iter().enumerate() {
+        if vertices.insert(no.id.clone(), i).is_some() {
+            return Err(erro(no.posicao, ParseErrorKind::DuplicateLabel(no.id.clone())));
+        }
+    }
+    let numeros: Option<Vec<usize>> = documento.nos.iter().map(|no| no.id.parse::<usize>().ok()).collect();
+    let base = numeros.and_then(|numeros| {
+        let n = numeros.len();
+        if numeros.iter().all(|&id| id < n) {
+            Some(0)
+        } else if numeros.iter().all(|&id| id >= 1 && id <= n) {
+            Some(1)
+        } else {
+            None
+        }
+    });
+    if let Some(base) = base {
+        for (id, vertice) in vertices.iter_mut() {
+            *vertice = id.parse::<usize>().expect("ids numéricos") - base;
+        }
+    }
+
+    let nomes: Vec<&str> = documento.attribute_names.iter().map(|nome| nome.as_str()).collect();
+    let nomes = if nomes.is_empty() { vec!["weight"] } else { nomes };
+    let mut grafo: Graph<W> = Graph::with_attributes(base != Some(1), documento.nos.len(), &nomes);
+    grafo.is_directed = documento.is_directed;
+
+    for aresta in &documento.arestas {
+        let origem = *vertices.get(&aresta.origem).ok_or_else(|| erro(aresta.posicao, ParseErrorKind::InvalidToken { token: aresta.origem.clone(), expected: "um nó declarado" }))?;
+        let destino = *vertices.get(&aresta.destino).ok_or_else(|| erro(aresta.posicao, ParseErrorKind::InvalidToken { token: aresta.destino.clone(), expected: "um nó declarado" }))?;
+
+        let mut valores = Vec::with_capacity(nomes.len());
+        for i in 0..nomes.len() {
+            let texto = aresta.valores.get(i).cloned().flatten().or_else(|| documento.padroes.get(i).cloned().flatten());
+            let valor = match texto {
+                None => W::zero(),
+                // O Gephi escreve pesos inteiros como "5.0"
+                Some(texto) => texto.parse::<W>().ok().or_else(|| texto.strip_suffix(".0").and_then(|t| t.parse::<W>().ok())).ok_or_else(|| {
+                    erro(aresta.posicao, ParseErrorKind::InvalidToken { token: texto.clone(), expected: "um peso" })
+                })?,
+            };
+            valores.push(valor);
+        }
+
+        grafo.edge_na_leitura(origem, destino, &valores).map_err(|_| {
+            let ajuste = base.unwrap_or(0);
+            erro(aresta.posicao, ParseErrorKind::DuplicateEdge { origin: origem + ajuste, destination: destino + ajuste })
+        })?;
+    }
+    grafo.terminar_leitura();
+
+    for no in documento.nos {
+        let vertice = vertices[&no.id];
+        match no.label {
+            // O Gephi sempre escreve o label, mesmo quando ele é só o id
+            Some(label) if base.is_none() || label != no.id => grafo.set_label(vertice, &label),
+            None if base.is_none() => grafo.set_label(vertice, &no.id),
+            _ => {}
+        }
+    }
+
+    Ok(grafo)
+}
+
+/// # Retorna o valor de um atributo do elemento, se existir
+
+fn atributo(elemento: &BytesStart, nome: &str) -> Option<String> {
+    elemento
+        .attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == nome.as_bytes())
+        .and_then(|a| a.unescape_value().ok().map(|valor| valor.into_owned()))
+}
+
+/// # Retorna o valor de um atributo obrigatório, ou o erro com a posição do elemento
+
+fn obrigatorio(path: &str, conteudo: &str, posicao: usize, elemento: &BytesStart, nome: &str) -> Result<String, GraphError> {
+    atributo(elemento, nome).ok_or_else(|| {
+        let tag = String::from_utf8_lossy(elemento.local_name().as_ref()).into_owned();
+        erro_xml(path, conteudo, posicao, format!("o elemento <{}> precisa do atributo \"{}\"", tag, nome))
+    })
+}
+
+/// # Cria o erro de XML inválido na posição (em bytes) indicada
+
+fn erro_xml(path: &str, conteudo: &str, posicao: usize, mensagem: String) -> GraphError {
+    let (linha, coluna) = linha_e_coluna(conteudo, posicao);
+    GraphError::parse(path, linha, coluna, ParseErrorKind::Xml(mensagem))
+}
+
+/// # Pula os espaços entre o fim do evento anterior e o próximo elemento
+
+fn inicio_do_elemento(conteudo: &str, posicao: u64) -> usize {
+    let posicao = (posicao as usize).min(conteudo.len());
+    posicao + conteudo[posicao..].len() - conteudo[posicao..].trim_start().len()
+}
+
+/// # Converte a posição em bytes para linha e coluna (1-based, em caracteres)
+
+fn linha_e_coluna(conteudo: &str, posicao: usize) -> (usize, usize) {
+    let antes = &conteudo[..posicao.min(conteudo.len())];
+    let linha = antes.matches('\n').count() + 1;
+    let inicio_da_linha = antes.rfind('\n').map_or(0, |i| i + 1);
+    (linha, antes[inicio_da_linha..].chars().count() + 1)
+}
+
+/// # Escapa os caracteres especiais do XML
+
+fn escapar(texto: &str) -> String {
+    texto
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::tests::{arestas_com_atributos, arquivo_temporario};
+
+    /// # Grafo 1-based não-direcionado com dois atributos e nomes que precisam ser escapados
+
+    fn grafo_de_exemplo() -> Graph {
+        let mut gr: Graph = Graph::with_attributes(false, 4, &["distance", "time"]);
+        gr.is_directed = false;
+        gr.edge_with_attributes(0, 1, &[5, 3]).unwrap();
+        gr.edge_with_attributes(2, 1, &[-2, 8]).unwrap();
+        gr.edge_with_attributes(3, 3, &[1, 1]).unwrap();
+        gr.set_label(1, "Praça <Sé> & \"Centro\"");
+        gr
+    }
+
+    fn erro(resultado: Result<Graph, GraphError>) -> (usize, usize, ParseErrorKind) {
+        match resultado {
+            Err(GraphError::Parse(erro)) => (erro.line, erro.column, erro.kind),
+            outro => panic!("esperado um erro de formatação, veio {:?}", outro.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn graphml_ida_e_volta() {
+        let gr = grafo_de_exemplo();
+        let mut xml: Vec<u8> = Vec::new();
+        write_graphml_to(&gr, &mut xml).unwrap();
+        let lido: Graph = read_graphml(&arquivo_temporario("ida.graphml", &xml)).unwrap();
+
+        assert_eq!((lido.is_zero_based, lido.is_directed, lido.num_vertex, lido.num_edges), (false, false, 4, 3));
+        assert_eq!(lido.attribute_names, gr.attribute_names);
+        assert_eq!(arestas_com_atributos(&lido), arestas_com_atributos(&gr));
+        assert_eq!((lido.label(0), lido.label(1)), (None, Some("Praça <Sé> & \"Centro\"")));
+    }
+
+    #[test]
+    fn gexf_ida_e_volta() {
+        let mut gr = grafo_de_exemplo();
+        gr.attribute_names = vec!["weight".to_string(), "time".to_string()];
+        let mut xml: Vec<u8> = Vec::new();
+        write_gexf_to(&gr, &mut xml).unwrap();
+        let lido: Graph = read_gexf(&arquivo_temporario("ida.gexf", &xml)).unwrap();
+
+        assert_eq!((lido.is_zero_based, lido.is_directed, lido.num_vertex, lido.num_edges), (false, false, 4, 3));
+        assert_eq!(lido.attribute_names, gr.attribute_names);
+        assert_eq!(arestas_com_atributos(&lido), arestas_com_atributos(&gr));
+        assert_eq!((lido.label(0), lido.label(1)), (None, Some("Praça <Sé> & \"Centro\"")));
+    }
+
+    #[test]
+    fn graphml_sem_peso_usa_1_e_ids_de_texto_viram_nomes() {
+        let xml = "<graphml>\n  <graph edgedefault=\"directed\">\n    <node id=\"a\"/>\n    <node id=\"b\"/>\n    <edge source=\"a\" target=\"b\"/>\n  </graph>\n</graphml>\n";
+        let lido: Graph = read_graphml(&arquivo_temporario("sem_peso.graphml", xml.as_bytes())).unwrap();
+        assert_eq!(lido.edges().collect::<Vec<_>>(), vec![(0, 1, 1)]);
+        assert_eq!((lido.label(0), lido.label(1)), (Some("a"), Some("b")));
+    }
+
+    #[test]
+    fn erros_apontam_o_elemento() {
+        let xml = "<graphml>\n  <graph edgedefault=\"directed\">\n    <node id=\"a\"/>\n    <edge source=\"a\" target=\"c\"/>\n  </graph>\n</graphml>\n";
+        assert_eq!(erro(read_graphml(&arquivo_temporario("sem_no.graphml", xml.as_bytes()))), (4, 5, ParseErrorKind::InvalidToken { token: "c".to_string(), expected: "um nó declarado" }));
+
+        let xml = "<gexf>\n  <graph>\n    <nodes><node id=\"0\"/><node id=\"1\"/></nodes>\n    <edges>\n      <edge source=\"0\" target=\"1\" weight=\"x\"/>\n    </edges>\n  </graph>\n</gexf>\n";
+        assert_eq!(erro(read_gexf(&arquivo_temporario("peso.gexf", xml.as_bytes()))), (5, 7, ParseErrorKind::InvalidToken { token: "x".to_string(), expected: "um peso" }));
+
+        let xml = "<graphml>\n  <graph edgedefault=\"directed\">\n    <edge target=\"a\"/>\n  </graph>\n</graphml>\n";
+        let (linha, coluna, kind) = erro(read_graphml(&arquivo_temporario("sem_source.graphml", xml.as_bytes())));
+        assert_eq!((linha, coluna), (3, 5));
+        assert!(matches!(kind, ParseErrorKind::Xml(mensagem) if mensagem.contains("\"source\"")));
+    }
+}